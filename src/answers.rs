@@ -0,0 +1,213 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use crate::{crypt, puzzle::Puzzle};
+use std::{fmt::Display, fs, io, path::Path};
+
+/// Accepted answers for a day, read from `src/answers/DD.toml` or `src/answers/YYYY/DD.toml`:
+///
+/// ```toml
+/// part_one = "24000"
+/// part_two = 45000
+/// ```
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part_one: Option<String>,
+    pub part_two: Option<String>,
+}
+
+/// Outcome of comparing a part's answer with the accepted one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Unverified,
+    Correct,
+    Wrong { expected: String },
+}
+
+impl Verdict {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Verdict::Unverified => "unverified",
+            Verdict::Correct => "correct",
+            Verdict::Wrong { .. } => "wrong",
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Unverified => write!(f, "? unverified"),
+            Verdict::Correct => write!(f, "✔ correct"),
+            Verdict::Wrong { expected } => write!(f, "✘ wrong, expected {}", expected),
+        }
+    }
+}
+
+impl Answers {
+    /// Loads the answers for a day. A missing file means no answer has been recorded yet.
+    pub fn load(puzzle: impl Into<Puzzle>) -> io::Result<Answers> {
+        let path = puzzle.into().path("answers", "toml")?;
+
+        match crypt::read_to_string(&path) {
+            Ok(contents) => Ok(Answers::parse(&contents)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Parses the `key = value` subset of TOML used by answer files. Unknown keys are ignored.
+    pub fn parse(contents: &str) -> Answers {
+        let mut answers = Answers::default();
+
+        for line in contents.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, value) = match line.split_once('=') {
+                Some((key, value)) => (key.trim(), value.trim()),
+                None => continue,
+            };
+
+            let value = value
+                .strip_prefix('"')
+                .and_then(|v| v.strip_suffix('"'))
+                .unwrap_or(value)
+                .to_string();
+
+            match key {
+                "part_one" => answers.part_one = Some(value),
+                "part_two" => answers.part_two = Some(value),
+                _ => {}
+            }
+        }
+
+        answers
+    }
+
+    /// Appends the accepted answer for `part` to the day's answer file, unless one is recorded.
+    /// The file is read like by [`Answers::load`], so answers only in its encrypted copy are kept.
+    pub fn record(puzzle: impl Into<Puzzle>, part: u8, answer: &str) -> io::Result<()> {
+        let path = puzzle.into().path("answers", "toml")?;
+        Answers::record_to(&path, part, answer, crypt::read_to_string)
+    }
+
+    fn record_to(
+        path: &Path,
+        part: u8,
+        answer: &str,
+        read: impl FnOnce(&Path) -> io::Result<String>,
+    ) -> io::Result<()> {
+        let mut contents = match read(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e),
+        };
+
+        let key = match part {
+            1 => "part_one",
+            _ => "part_two",
+        };
+        if Answers::parse(&contents).get(part).is_some() {
+            return Ok(());
+        }
+
+        if !contents.is_empty() && !contents.ends_with('\n') {
+            contents.push('\n');
+        }
+        contents.push_str(&format!("{} = \"{}\"\n", key, answer));
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, contents)
+    }
+
+    pub fn get(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_one.as_deref(),
+            2 => self.part_two.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Compares `actual` with the recorded answer for `part`.
+    /// A part that is no longer solved counts as wrong if an answer was recorded.
+    pub fn check(&self, part: u8, actual: Option<&str>) -> Verdict {
+        match self.get(part) {
+            None => Verdict::Unverified,
+            Some(expected) if Some(expected) == actual => Verdict::Correct,
+            Some(expected) => Verdict::Wrong {
+                expected: expected.to_string(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let answers =
+            Answers::parse("# day 5\npart_one = \"CMZ\"\n\npart_two=12\nunknown = \"ignored\"\n");
+        assert_eq!(
+            answers,
+            Answers {
+                part_one: Some("CMZ".into()),
+                part_two: Some("12".into()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_record_encrypted() {
+        let root = std::env::temp_dir().join(format!("aoc-answers-{}", std::process::id()));
+        let path = root.join("src/answers/01.toml");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        let key = crypt::Key::generate();
+        fs::write(
+            crypt::encrypted_path(&path),
+            crypt::encrypt(&key, "src/answers/01.toml", "part_one = \"24000\"\n"),
+        )
+        .unwrap();
+
+        let read = |path: &Path| crypt::read_in(&root, path, || Ok(Some(key.clone())));
+        Answers::record_to(&path, 2, "45000", read).unwrap();
+        assert_eq!(
+            Answers::parse(&fs::read_to_string(&path).unwrap()),
+            Answers {
+                part_one: Some("24000".into()),
+                part_two: Some("45000".into()),
+            }
+        );
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_check() {
+        let answers = Answers {
+            part_one: Some("24000".into()),
+            part_two: None,
+        };
+        assert_eq!(answers.check(1, Some("24000")), Verdict::Correct);
+        assert_eq!(
+            answers.check(1, Some("24001")),
+            Verdict::Wrong {
+                expected: "24000".into()
+            }
+        );
+        assert_eq!(
+            answers.check(1, None),
+            Verdict::Wrong {
+                expected: "24000".into()
+            }
+        );
+        assert_eq!(answers.check(2, Some("45000")), Verdict::Unverified);
+    }
+}
@@ -0,0 +1,352 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use crate::{
+    html, inputs,
+    profiles::{self, ProfileError, Profiles},
+    puzzle,
+    puzzle::Puzzle,
+};
+use std::{
+    env,
+    fmt::Display,
+    fs::{self, create_dir_all},
+    io,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+/// Environment variable overriding the address of the Advent of Code website.
+pub const BASE_URL_ENV: &str = "AOC_BASE_URL";
+/// Environment variable holding the session cookie. Takes precedence over the session file.
+pub const SESSION_ENV: &str = "AOC_SESSION";
+/// Environment variable overriding the user agent sent with every request,
+/// e.g. to add your contact details.
+pub const USER_AGENT_ENV: &str = "AOC_USER_AGENT";
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const SESSION_FILE: &str = ".adventofcode.session";
+const DEFAULT_USER_AGENT: &str = "github.com/5cat/advant-of-code-rust-2022";
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug)]
+pub enum AocError {
+    SessionNotFound,
+    /// The site rejected the session cookie, usually because it expired.
+    BadSession,
+    /// The puzzle does not exist or is not unlocked yet.
+    NotFound(Puzzle),
+    BadStatus(u16),
+    Transport(String),
+    NoPuzzleDescription,
+    /// The session cookie of the active profile could not be read.
+    Profile(ProfileError),
+    IoError(io::Error),
+}
+
+impl Display for AocError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocError::SessionNotFound => write!(
+                f,
+                "no session cookie found. Set {} or create \"~/{}\".",
+                SESSION_ENV, SESSION_FILE
+            ),
+            AocError::BadSession => write!(
+                f,
+                "the session cookie was rejected. It might have expired, try refreshing it."
+            ),
+            AocError::NotFound(puzzle) => {
+                write!(f, "{} does not exist or is not unlocked yet.", puzzle)
+            }
+            AocError::BadStatus(status) => {
+                write!(f, "the server responded with status {}.", status)
+            }
+            AocError::Transport(e) => write!(f, "could not reach the server: {}", e),
+            AocError::NoPuzzleDescription => {
+                write!(f, "the puzzle page contains no description.")
+            }
+            AocError::Profile(e) => write!(f, "{}", e),
+            AocError::IoError(e) => write!(f, "could not write output files to file system: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for AocError {}
+
+impl From<io::Error> for AocError {
+    fn from(e: io::Error) -> Self {
+        AocError::IoError(e)
+    }
+}
+
+/// Reads the session cookie from `AOC_SESSION` or `~/.adventofcode.session`.
+/// With a profile, the cookie stored for it is used instead.
+pub fn read_session() -> Result<String, AocError> {
+    if let Some(profile) = profiles::active() {
+        return Profiles::from_home()
+            .and_then(|profiles| profiles.session(profile))
+            .map_err(AocError::Profile);
+    }
+
+    let session = match env::var(SESSION_ENV) {
+        Ok(session) => session,
+        Err(_) => session_path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .ok_or(AocError::SessionNotFound)?,
+    };
+
+    let session = session.trim();
+    if session.is_empty() {
+        return Err(AocError::SessionNotFound);
+    }
+    Ok(session.to_string())
+}
+
+fn session_path() -> Option<PathBuf> {
+    let home = env::var_os("HOME").or_else(|| env::var_os("USERPROFILE"))?;
+    Some(Path::new(&home).join(SESSION_FILE))
+}
+
+/// Returns `AOC_BASE_URL`, falling back to the Advent of Code website.
+pub fn base_url() -> String {
+    match env::var(BASE_URL_ENV) {
+        Ok(url) if !url.trim().is_empty() => url.trim().to_string(),
+        _ => DEFAULT_BASE_URL.to_string(),
+    }
+}
+
+/// Returns `AOC_USER_AGENT`, falling back to this repository.
+pub fn user_agent() -> String {
+    match env::var(USER_AGENT_ENV) {
+        Ok(agent) if !agent.trim().is_empty() => agent.trim().to_string(),
+        _ => DEFAULT_USER_AGENT.to_string(),
+    }
+}
+
+fn map_error(e: ureq::Error, puzzle: Puzzle) -> AocError {
+    match e {
+        ureq::Error::Status(400 | 401 | 403, _) => AocError::BadSession,
+        ureq::Error::Status(404, _) => AocError::NotFound(puzzle),
+        ureq::Error::Status(status, _) => AocError::BadStatus(status),
+        ureq::Error::Transport(e) => AocError::Transport(e.to_string()),
+    }
+}
+
+/// Client for the Advent of Code website.
+pub struct AocClient {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, session: &str) -> AocClient {
+        AocClient {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            agent: ureq::AgentBuilder::new()
+                .timeout(TIMEOUT)
+                .user_agent(&user_agent())
+                .build(),
+        }
+    }
+
+    /// Creates a client for `AOC_BASE_URL` with the session of `read_session`.
+    pub fn from_env() -> Result<AocClient, AocError> {
+        Ok(AocClient::new(&base_url(), &read_session()?))
+    }
+
+    /// Year of the event the puzzle belongs to. Days without a year belong to the current event.
+    pub fn event_year(puzzle: Puzzle) -> u16 {
+        puzzle
+            .year
+            .unwrap_or_else(|| puzzle::event_year(SystemTime::now()))
+    }
+
+    fn day_url(&self, puzzle: Puzzle) -> String {
+        format!(
+            "{}/{}/day/{}",
+            self.base_url,
+            AocClient::event_year(puzzle),
+            puzzle.day
+        )
+    }
+
+    fn get(&self, url: &str, puzzle: Puzzle) -> Result<String, AocError> {
+        let response = self
+            .agent
+            .get(url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call()
+            .map_err(|e| map_error(e, puzzle))?;
+
+        response
+            .into_string()
+            .map_err(|e| AocError::Transport(e.to_string()))
+    }
+
+    /// Fetches the puzzle input of the session's user.
+    pub fn get_input(&self, puzzle: Puzzle) -> Result<String, AocError> {
+        self.get(&format!("{}/input", self.day_url(puzzle)), puzzle)
+    }
+
+    /// Fetches the puzzle description as markdown.
+    pub fn get_puzzle(&self, puzzle: Puzzle) -> Result<String, AocError> {
+        let html = self.get(&self.day_url(puzzle), puzzle)?;
+        html::puzzle_to_markdown(&html).ok_or(AocError::NoPuzzleDescription)
+    }
+
+    /// Posts an answer and returns the text of the response page.
+    pub fn post_answer(&self, puzzle: Puzzle, part: u8, answer: &str) -> Result<String, AocError> {
+        let url = format!("{}/answer", self.day_url(puzzle));
+        let html = self
+            .agent
+            .post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])
+            .map_err(|e| map_error(e, puzzle))?
+            .into_string()
+            .map_err(|e| AocError::Transport(e.to_string()))?;
+
+        // the response is a single article, convert it to read its text.
+        Ok(html::puzzle_to_markdown(&html).unwrap_or(html))
+    }
+
+    /// Writes the input and description of a puzzle to `src/inputs` and `src/puzzles`,
+    /// and the checksum of the input to `src/inputs`.
+    pub fn download(&self, puzzle: Puzzle) -> Result<(PathBuf, PathBuf), AocError> {
+        let input = self.get_input(puzzle)?;
+        let description = self.get_puzzle(puzzle)?;
+
+        let input_path = puzzle.relative_path("inputs", "txt");
+        let puzzle_path = puzzle.relative_path("puzzles", "md");
+
+        for (path, contents) in [(&input_path, &input), (&puzzle_path, &description)] {
+            if let Some(dir) = path.parent() {
+                create_dir_all(dir)?;
+            }
+            fs::write(path, contents)?;
+        }
+        inputs::save_checksum(puzzle, &input)?;
+
+        Ok((input_path, puzzle_path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        thread::{self, JoinHandle},
+    };
+
+    /// Serves the given responses, one per connection, and returns the requests it received.
+    fn serve(responses: Vec<(u16, &'static str)>) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let mut requests = vec![];
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut request = String::new();
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    request.push_str(&line);
+                }
+                requests.push(request);
+
+                write!(
+                    reader.get_mut(),
+                    "HTTP/1.1 {} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+            requests
+        });
+
+        (url, handle)
+    }
+
+    #[test]
+    fn test_get_input() {
+        let (url, server) = serve(vec![(200, "1000\n2000\n")]);
+        let client = AocClient::new(&url, "abc");
+
+        let input = client.get_input(Puzzle::from((2021, 7))).unwrap();
+        assert_eq!(input, "1000\n2000\n");
+
+        let request = &server.join().unwrap()[0];
+        assert!(request.starts_with("GET /2021/day/7/input HTTP/1.1"));
+        assert!(request.contains("session=abc"));
+    }
+
+    #[test]
+    fn test_get_puzzle() {
+        let page = "<main><article class=\"day-desc\"><h2>--- Day 7 ---</h2><p>Hi &amp; bye.</p></article></main>";
+        let (url, server) = serve(vec![(200, page)]);
+        let client = AocClient::new(&url, "abc");
+
+        let puzzle = client.get_puzzle(Puzzle::from((2021, 7))).unwrap();
+        assert_eq!(puzzle, "## --- Day 7 ---\n\nHi & bye.\n");
+
+        let request = &server.join().unwrap()[0];
+        assert!(request.starts_with("GET /2021/day/7 HTTP/1.1"));
+    }
+
+    #[test]
+    fn test_errors() {
+        let (url, server) = serve(vec![
+            (
+                400,
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.",
+            ),
+            (
+                404,
+                "Please don't repeatedly request this endpoint before it unlocks!",
+            ),
+            (500, ""),
+            (200, "<html>no article</html>"),
+        ]);
+        let client = AocClient::new(&url, "abc");
+        let puzzle = Puzzle::from((2021, 7));
+
+        assert!(matches!(
+            client.get_input(puzzle),
+            Err(AocError::BadSession)
+        ));
+        assert!(matches!(
+            client.get_input(puzzle),
+            Err(AocError::NotFound(p)) if p == puzzle
+        ));
+        assert!(matches!(
+            client.get_input(puzzle),
+            Err(AocError::BadStatus(500))
+        ));
+        assert!(matches!(
+            client.get_puzzle(puzzle),
+            Err(AocError::NoPuzzleDescription)
+        ));
+        server.join().unwrap();
+
+        let closed = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", closed.local_addr().unwrap());
+        drop(closed);
+        assert!(matches!(
+            AocClient::new(&url, "abc").get_input(puzzle),
+            Err(AocError::Transport(_))
+        ));
+    }
+}
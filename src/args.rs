@@ -0,0 +1,309 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use crate::profiles;
+use std::{path::PathBuf, process, str::FromStr, sync::OnceLock, time::Duration};
+
+/// Baseline used by `--save-baseline` and `--compare` unless `--baseline` is passed.
+pub const DEFAULT_BASELINE: &str = "baseline";
+/// Slowdown in percent above which `--compare` reports a regression.
+pub const DEFAULT_THRESHOLD: f64 = 10_f64;
+
+/// Flags shared by the day binaries and `cargo all -- <flags>`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunArgs {
+    /// Run every part many times and report timing statistics.
+    pub bench: bool,
+    /// Only run this part.
+    pub part: Option<u8>,
+    /// Store the timings in the baseline file.
+    pub save_baseline: bool,
+    /// Compare the timings with the baseline file.
+    pub compare: bool,
+    /// Name of the baseline file in `src/benchmarks`.
+    pub baseline: String,
+    /// Slowdown in percent that `--compare` reports as a regression.
+    pub threshold: f64,
+    /// Time after which a part is reported as timed out.
+    pub timeout: Option<Duration>,
+    /// Time after which the remaining parts of a day are reported as timed out.
+    pub day_timeout: Option<Duration>,
+    /// Profile whose inputs and answers are used, see `cargo profile`.
+    pub profile: Option<String>,
+    /// Set when a day runs against another input than `src/inputs/DD.txt`.
+    /// Recorded answers and baselines do not apply to such runs.
+    pub custom_input: bool,
+}
+
+impl Default for RunArgs {
+    fn default() -> Self {
+        RunArgs {
+            bench: false,
+            part: None,
+            save_baseline: false,
+            compare: false,
+            baseline: DEFAULT_BASELINE.into(),
+            threshold: DEFAULT_THRESHOLD,
+            timeout: None,
+            day_timeout: None,
+            profile: None,
+            custom_input: false,
+        }
+    }
+}
+
+impl RunArgs {
+    /// Extracts the shared flags from `args`, leaving any other arguments in place.
+    pub fn parse(args: &mut pico_args::Arguments) -> Result<RunArgs, pico_args::Error> {
+        let part = args.opt_value_from_fn(["-p", "--part"], |val| match val {
+            "1" => Ok(1),
+            "2" => Ok(2),
+            _ => Err("part must be 1 or 2"),
+        })?;
+
+        Ok(RunArgs {
+            bench: args.contains("--bench"),
+            part,
+            save_baseline: args.contains("--save-baseline"),
+            compare: args.contains("--compare"),
+            baseline: args
+                .opt_value_from_str("--baseline")?
+                .unwrap_or_else(|| DEFAULT_BASELINE.into()),
+            threshold: args
+                .opt_value_from_str("--threshold")?
+                .unwrap_or(DEFAULT_THRESHOLD),
+            timeout: args.opt_value_from_fn("--timeout", parse_seconds)?,
+            day_timeout: args.opt_value_from_fn("--day-timeout", parse_seconds)?,
+            profile: args.opt_value_from_str("--profile")?,
+            custom_input: false,
+        })
+    }
+
+    /// Selects `--profile`, or `AOC_PROFILE`, for this process. Exits if the name is invalid.
+    pub fn activate_profile(&self) {
+        if let Err(e) = profiles::activate(self.profile.clone()) {
+            eprintln!("Failed to process arguments: {}", e);
+            process::exit(1);
+        }
+    }
+
+    /// Whether `part` should run with these flags.
+    pub fn includes_part(&self, part: u8) -> bool {
+        self.part.is_none_or(|p| p == part)
+    }
+}
+
+/// Where a day binary reads its puzzle input from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    /// `src/inputs/DD.txt`, used unless `--input` is passed.
+    Default,
+    /// `--input -`
+    Stdin,
+    /// `--input <path>`
+    File(PathBuf),
+}
+
+impl FromStr for InputSource {
+    type Err = String;
+
+    fn from_str(val: &str) -> Result<Self, Self::Err> {
+        match val {
+            "" => Err("input path must not be empty".into()),
+            "-" => Ok(InputSource::Stdin),
+            path => Ok(InputSource::File(path.into())),
+        }
+    }
+}
+
+/// Flags accepted by the day binaries: `cargo solve DD -- <flags>`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DayArgs {
+    pub run: RunArgs,
+    pub input: InputSource,
+}
+
+impl DayArgs {
+    pub fn parse(mut args: pico_args::Arguments) -> Result<DayArgs, pico_args::Error> {
+        let mut run = RunArgs::parse(&mut args)?;
+        let input = args
+            .opt_value_from_str(["-i", "--input"])?
+            .unwrap_or(InputSource::Default);
+        finish(args)?;
+
+        run.custom_input = input != InputSource::Default;
+        Ok(DayArgs { run, input })
+    }
+
+    /// Returns the flags passed to the current day binary, exiting if they are invalid.
+    pub fn from_env() -> &'static DayArgs {
+        static ARGS: OnceLock<DayArgs> = OnceLock::new();
+        ARGS.get_or_init(|| {
+            let args = DayArgs::parse(pico_args::Arguments::from_env()).unwrap_or_else(|e| {
+                eprintln!("Failed to process arguments: {}", e);
+                process::exit(1);
+            });
+            args.run.activate_profile();
+            args
+        })
+    }
+}
+
+/// Parses a positive number of seconds, e.g. `2.5`.
+pub fn parse_seconds(val: &str) -> Result<Duration, String> {
+    match val.parse::<f64>() {
+        Ok(secs) if secs.is_finite() && secs > 0_f64 => Ok(Duration::from_secs_f64(secs)),
+        _ => Err(format!("\"{}\" is not a positive number of seconds", val)),
+    }
+}
+
+/// Parses a list of days and inclusive day ranges, e.g. `1,3,5-9`.
+/// The result is sorted and free of duplicates.
+pub fn parse_days(val: &str) -> Result<Vec<u8>, String> {
+    let parse_day = |day: &str| match day.trim().parse::<u8>() {
+        Ok(day) if (1..=25).contains(&day) => Ok(day),
+        _ => Err(format!("\"{}\" is not a day between 1 and 25", day.trim())),
+    };
+
+    let mut days = vec![];
+    for item in val.split(',').filter(|item| !item.trim().is_empty()) {
+        match item.split_once('-') {
+            Some((start, end)) => {
+                let (start, end) = (parse_day(start)?, parse_day(end)?);
+                if start > end {
+                    return Err(format!("\"{}\" is not an ascending range", item.trim()));
+                }
+                days.extend(start..=end);
+            }
+            None => days.push(parse_day(item)?),
+        }
+    }
+
+    if days.is_empty() {
+        return Err("no days given".into());
+    }
+
+    days.sort_unstable();
+    days.dedup();
+    Ok(days)
+}
+
+/// Fails if `args` still contains an option, e.g. a misspelled one. Call it once all options
+/// are parsed, so that a typo is not taken for a free argument. Negative numbers are kept.
+pub fn reject_options(args: &pico_args::Arguments) -> Result<(), pico_args::Error> {
+    let remaining = args.clone().finish();
+    let option = remaining.iter().find(|arg| {
+        let arg = arg.to_string_lossy();
+        arg.starts_with('-') && !arg[1..].starts_with(|c: char| c.is_ascii_digit())
+    });
+    match option {
+        Some(arg) => Err(pico_args::Error::ArgumentParsingFailed {
+            cause: format!("unexpected argument {:?}", arg),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Fails if `args` contains arguments that were not consumed by the parser.
+pub fn finish(args: pico_args::Arguments) -> Result<(), pico_args::Error> {
+    match args.finish().first() {
+        Some(arg) => Err(pico_args::Error::ArgumentParsingFailed {
+            cause: format!("unexpected argument {:?}", arg),
+        }),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<RunArgs, pico_args::Error> {
+        let mut args = pico_args::Arguments::from_vec(args.iter().map(|a| a.into()).collect());
+        RunArgs::parse(&mut args)
+    }
+
+    #[test]
+    fn test_reject_options() {
+        let args =
+            |args: &[&str]| pico_args::Arguments::from_vec(args.iter().map(|a| a.into()).collect());
+        assert!(reject_options(&args(&["1", "2", "-42"])).is_ok());
+        assert!(matches!(
+            reject_options(&args(&["--templte", "grid", "1"])),
+            Err(pico_args::Error::ArgumentParsingFailed { cause }) if cause.contains("--templte")
+        ));
+        assert!(reject_options(&args(&["1", "-x"])).is_err());
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse(&[]).unwrap(), RunArgs::default());
+        assert_eq!(
+            parse(&["--bench", "--part", "2"]).unwrap(),
+            RunArgs {
+                bench: true,
+                part: Some(2),
+                ..RunArgs::default()
+            }
+        );
+        assert_eq!(
+            parse(&["--compare", "--baseline", "before", "--threshold", "2.5"]).unwrap(),
+            RunArgs {
+                compare: true,
+                baseline: "before".into(),
+                threshold: 2.5,
+                ..RunArgs::default()
+            }
+        );
+        assert_eq!(
+            parse(&["--profile", "alice"]).unwrap(),
+            RunArgs {
+                profile: Some("alice".into()),
+                ..RunArgs::default()
+            }
+        );
+        assert_eq!(
+            parse(&["--timeout", "0.5", "--day-timeout", "2"]).unwrap(),
+            RunArgs {
+                timeout: Some(Duration::from_millis(500)),
+                day_timeout: Some(Duration::from_secs(2)),
+                ..RunArgs::default()
+            }
+        );
+        assert!(parse(&["-p", "3"]).is_err());
+        assert!(parse(&["--timeout", "0"]).is_err());
+        assert!(parse(&["--timeout", "soon"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_day_args() {
+        let parse = |args: &[&str]| {
+            DayArgs::parse(pico_args::Arguments::from_vec(
+                args.iter().map(|a| a.into()).collect(),
+            ))
+        };
+
+        assert_eq!(parse(&[]).unwrap().input, InputSource::Default);
+        assert_eq!(parse(&["--input", "-"]).unwrap().input, InputSource::Stdin);
+
+        let args = parse(&["--bench", "-i", "inputs/colleague.txt"]).unwrap();
+        assert!(args.run.bench);
+        assert!(args.run.custom_input);
+        assert_eq!(args.input, InputSource::File("inputs/colleague.txt".into()));
+
+        assert!(parse(&["--days", "1"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_days() {
+        assert_eq!(parse_days("7"), Ok(vec![7]));
+        assert_eq!(parse_days("1,3,5-9"), Ok(vec![1, 3, 5, 6, 7, 8, 9]));
+        assert_eq!(parse_days("9-10, 3,9"), Ok(vec![3, 9, 10]));
+        assert!(parse_days("0").is_err());
+        assert!(parse_days("24-26").is_err());
+        assert!(parse_days("9-5").is_err());
+        assert!(parse_days("a").is_err());
+        assert!(parse_days("").is_err());
+    }
+}
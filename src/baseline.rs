@@ -0,0 +1,182 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use crate::{
+    profiles,
+    results::{self, PartResult},
+};
+use std::{env, fmt::Display, fs, io, path::PathBuf, time::Duration};
+
+/// Saved timings of every part, stored as JSON Lines in `src/benchmarks/<name>.jsonl`.
+/// Baselines of a year are stored in `src/benchmarks/YYYY/<name>.jsonl`, and those of a profile
+/// in `src/benchmarks/<profile>/...`: timings depend on the input they were taken on.
+#[derive(Debug, Default)]
+pub struct Baseline {
+    records: Vec<PartResult>,
+}
+
+impl Baseline {
+    fn path(year: Option<u16>, name: &str) -> io::Result<PathBuf> {
+        let mut path = env::current_dir()?.join("src").join("benchmarks");
+        if let Some(profile) = profiles::active() {
+            path.push(profile);
+        }
+        if let Some(year) = year {
+            path.push(year.to_string());
+        }
+        Ok(path.join(format!("{}.jsonl", name)))
+    }
+
+    /// Loads the baseline called `name`. A missing file is treated as an empty baseline.
+    pub fn load(year: Option<u16>, name: &str) -> io::Result<Baseline> {
+        match fs::read_to_string(Baseline::path(year, name)?) {
+            Ok(contents) => {
+                let mut records = results::parse_records(&contents);
+                // records saved before years were recorded belong to the year of their file.
+                for record in records.iter_mut().filter(|r| r.year.is_none()) {
+                    record.year = year;
+                }
+                Ok(Baseline { records })
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Baseline::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, year: Option<u16>, name: &str) -> io::Result<()> {
+        let path = Baseline::path(year, name)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let contents: String = self
+            .records
+            .iter()
+            .map(|r| format!("{}\n", r.to_json()))
+            .collect();
+        fs::write(path, contents)
+    }
+
+    /// Looks up the saved timing of a part. Records of other years never match,
+    /// even if they ended up in the same file.
+    pub fn get(&self, year: Option<u16>, day: u8, part: u8) -> Option<&PartResult> {
+        self.records
+            .iter()
+            .find(|r| (r.year, r.day, r.part) == (year, day, part))
+    }
+
+    /// Replaces the saved timing for the result's year, day and part, keeping other parts untouched.
+    pub fn update(&mut self, result: &PartResult) {
+        let key = |r: &PartResult| (r.year, r.day, r.part);
+        self.records.retain(|r| key(r) != key(result));
+        self.records.push(result.clone());
+        self.records.sort_by_key(key);
+    }
+
+    /// Compares a fresh result with its saved timing, if there is one.
+    pub fn compare(&self, result: &PartResult) -> Option<Comparison> {
+        self.get(result.year, result.day, result.part)
+            .map(|saved| Comparison {
+                before: saved.elapsed,
+                after: result.elapsed,
+            })
+    }
+}
+
+/// Change in timing of one part relative to the baseline.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Comparison {
+    pub before: Duration,
+    pub after: Duration,
+}
+
+impl Comparison {
+    /// Relative change in percent. Positive values mean the part got slower.
+    pub fn change(&self) -> f64 {
+        if self.before.is_zero() {
+            return 0_f64;
+        }
+        (self.after.as_secs_f64() / self.before.as_secs_f64() - 1_f64) * 100_f64
+    }
+
+    /// Whether the part got slower by more than `threshold` percent.
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.change() > threshold
+    }
+}
+
+impl Display for Comparison {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let change = self.change();
+        write!(
+            f,
+            "{:.2?} → {:.2?} ({:+.1}%, {})",
+            self.before,
+            self.after,
+            change,
+            if change > 0_f64 { "slower" } else { "faster" }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{answers::Verdict, results::Outcome};
+
+    fn result(day: u8, part: u8, micros: u64) -> PartResult {
+        PartResult {
+            year: None,
+            day,
+            part,
+            outcome: Outcome::Solved("0".into()),
+            elapsed: Duration::from_micros(micros),
+            verdict: Verdict::Unverified,
+            memory: None,
+        }
+    }
+
+    #[test]
+    fn test_update() {
+        let mut baseline = Baseline::default();
+        baseline.update(&result(8, 1, 100));
+        baseline.update(&result(1, 2, 10));
+        baseline.update(&result(8, 1, 50));
+
+        assert_eq!(baseline.records, vec![result(1, 2, 10), result(8, 1, 50)]);
+    }
+
+    #[test]
+    fn test_compare() {
+        let mut baseline = Baseline::default();
+        baseline.update(&result(8, 1, 100));
+
+        let comparison = baseline.compare(&result(8, 1, 150)).unwrap();
+        assert!((comparison.change() - 50_f64).abs() < 1.0e-6);
+        assert!(comparison.is_regression(10_f64));
+        assert!(!comparison.is_regression(60_f64));
+        assert_eq!(
+            comparison.to_string(),
+            "100.00µs → 150.00µs (+50.0%, slower)"
+        );
+
+        let comparison = baseline.compare(&result(8, 1, 75)).unwrap();
+        assert!(!comparison.is_regression(0_f64));
+        assert_eq!(
+            comparison.to_string(),
+            "100.00µs → 75.00µs (-25.0%, faster)"
+        );
+
+        assert_eq!(baseline.compare(&result(8, 2, 75)), None);
+
+        let other_year = PartResult {
+            year: Some(2021),
+            ..result(8, 1, 75)
+        };
+        assert_eq!(baseline.compare(&other_year), None);
+        baseline.update(&other_year);
+        assert_eq!(baseline.get(None, 8, 1), Some(&result(8, 1, 100)));
+        assert_eq!(baseline.get(Some(2021), 8, 1), Some(&other_year));
+    }
+}
@@ -0,0 +1,120 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::{
+    fmt::Display,
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+/// Time spent running a part before measurements start.
+const WARMUP_TIME: Duration = Duration::from_millis(100);
+/// Approximate time spent measuring a part once warmed up.
+const MEASUREMENT_TIME: Duration = Duration::from_secs(1);
+const MIN_ITERATIONS: u32 = 5;
+const MAX_ITERATIONS: u32 = 10_000;
+
+/// Summary of repeated timings of a single part.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats {
+    pub iterations: u32,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    /// Computes statistics over a non-empty set of samples.
+    pub fn from_samples(samples: &mut [Duration]) -> Stats {
+        assert!(!samples.is_empty(), "need at least one sample");
+        samples.sort();
+
+        let n = samples.len();
+        let median = if n.is_multiple_of(2) {
+            (samples[n / 2 - 1] + samples[n / 2]) / 2
+        } else {
+            samples[n / 2]
+        };
+
+        let mean = samples.iter().sum::<Duration>() / n as u32;
+        let variance = samples
+            .iter()
+            .map(|s| (s.as_secs_f64() - mean.as_secs_f64()).powi(2))
+            .sum::<f64>()
+            / n as f64;
+
+        Stats {
+            iterations: n as u32,
+            min: samples[0],
+            median,
+            mean,
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "min: {:.2?}, median: {:.2?}, mean: {:.2?} ± {:.2?}, {} iterations",
+            self.min, self.median, self.mean, self.stddev, self.iterations
+        )
+    }
+}
+
+/// Runs `func` repeatedly after a warm-up phase and returns its last output with timing statistics.
+pub fn measure<T>(mut func: impl FnMut() -> T) -> (T, Stats) {
+    let warmup = Instant::now();
+    let mut warmup_runs = 0_u32;
+    while warmup_runs == 0 || warmup.elapsed() < WARMUP_TIME {
+        black_box(func());
+        warmup_runs += 1;
+    }
+
+    let per_run = warmup.elapsed() / warmup_runs;
+    let iterations = (MEASUREMENT_TIME.as_nanos() / per_run.as_nanos().max(1))
+        .clamp(MIN_ITERATIONS as u128, MAX_ITERATIONS as u128) as usize;
+
+    let mut samples = Vec::with_capacity(iterations);
+    let mut output = None;
+    for _ in 0..iterations {
+        let timer = Instant::now();
+        let result = black_box(func());
+        samples.push(timer.elapsed());
+        output = Some(result);
+    }
+
+    (output.unwrap(), Stats::from_samples(&mut samples))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_samples() {
+        let mut samples = [4, 1, 3, 2].map(Duration::from_micros);
+        let stats = Stats::from_samples(&mut samples);
+        assert_eq!(stats.iterations, 4);
+        assert_eq!(stats.min, Duration::from_micros(1));
+        assert_eq!(stats.median, Duration::from_nanos(2500));
+        assert_eq!(stats.mean, Duration::from_nanos(2500));
+        // population stddev of 1, 2, 3, 4 is sqrt(1.25).
+        assert_eq!(stats.stddev.as_nanos(), 1118);
+    }
+
+    #[test]
+    fn test_measure() {
+        let mut calls = 0;
+        let (output, stats) = measure(|| {
+            calls += 1;
+            calls
+        });
+        assert!(stats.iterations >= MIN_ITERATIONS);
+        assert!(stats.iterations <= MAX_ITERATIONS);
+        assert_eq!(output, calls);
+    }
+}
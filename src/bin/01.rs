@@ -26,13 +26,13 @@ pub fn part_two(input: &str) -> Option<u32> {
         .iter()
         .rev()
         .take(3)
-        .map(|x| x.clone() as f32)
+        .map(|x| *x as f32)
         .sum::<f32>() as u32;
     Some(top_3_elves)
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 1);
+    let input = &advent_of_code::read_input(1);
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
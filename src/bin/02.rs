@@ -29,7 +29,7 @@ fn score(me: &Move, op: &Move) -> u32 {
     };
 
     if me == op {
-        match_result = match_result + 3;
+        match_result += 3;
     }
 
     move_value + match_result
@@ -53,7 +53,7 @@ fn convert_to_wanted_move(op: &Move, wanted: &Move) -> Move {
             Move::Paper => Move::Rock,
             Move::Sicssors => Move::Paper,
         },
-        Move::Paper => (*op).clone(),
+        Move::Paper => *op,
         Move::Sicssors => match op {
             Move::Rock => Move::Paper,
             Move::Paper => Move::Sicssors,
@@ -73,7 +73,7 @@ pub fn part_two(input: &str) -> Option<u32> {
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 2);
+    let input = &advent_of_code::read_input(2);
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
@@ -15,7 +15,6 @@ pub fn part_one(input: &str) -> Option<u32> {
             HashSet::from_iter(compartment_2.chars().filter(|x| *x != '\n'));
         let diff: &char = compartment_1_set
             .intersection(&compartment_2_set)
-            .into_iter()
             .next()
             .unwrap();
         if diff.is_lowercase() {
@@ -44,25 +43,21 @@ pub fn part_two(input: &str) -> Option<u32> {
         let second_elve = &elves[1];
         let third_elve = &elves[2];
         let common_1_2_elve = first_elve
-            .intersection(&second_elve)
-            .map(|x| *x)
+            .intersection(second_elve)
+            .copied()
             .collect::<HashSet<char>>();
-        let common_clear_char = common_1_2_elve
-            .intersection(&third_elve)
-            .into_iter()
-            .next()
-            .unwrap();
+        let common_clear_char = common_1_2_elve.intersection(third_elve).next().unwrap();
         if common_clear_char.is_lowercase() {
-            score = score + (common_clear_char.clone() as u32 - 'a' as u32) + 1
+            score = score + (*common_clear_char as u32 - 'a' as u32) + 1
         } else {
-            score = score + (common_clear_char.clone() as u32 - 'A' as u32) + 27
+            score = score + (*common_clear_char as u32 - 'A' as u32) + 27
         }
     }
     Some(score)
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 3);
+    let input = &advent_of_code::read_input(3);
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
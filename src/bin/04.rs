@@ -11,13 +11,10 @@ pub fn part_one(input: &str) -> Option<u32> {
         .lines()
         .filter(|x| !x.is_empty())
         .map(|x| {
-            let mut x_t = x
-                .split(',')
-                .map(|y| {
-                    let (y_1, y_2) = y.split_once('-').unwrap();
-                    (y_1.parse::<u32>().unwrap(), y_2.parse::<u32>().unwrap())
-                })
-                .into_iter();
+            let mut x_t = x.split(',').map(|y| {
+                let (y_1, y_2) = y.split_once('-').unwrap();
+                (y_1.parse::<u32>().unwrap(), y_2.parse::<u32>().unwrap())
+            });
             (x_t.next().unwrap(), x_t.next().unwrap())
         })
         .collect::<Vec<_>>();
@@ -35,13 +32,10 @@ pub fn part_two(input: &str) -> Option<u32> {
         .lines()
         .filter(|x| !x.is_empty())
         .map(|x| {
-            let mut x_t = x
-                .split(',')
-                .map(|y| {
-                    let (y_1, y_2) = y.split_once('-').unwrap();
-                    (y_1.parse::<u32>().unwrap(), y_2.parse::<u32>().unwrap())
-                })
-                .into_iter();
+            let mut x_t = x.split(',').map(|y| {
+                let (y_1, y_2) = y.split_once('-').unwrap();
+                (y_1.parse::<u32>().unwrap(), y_2.parse::<u32>().unwrap())
+            });
             (x_t.next().unwrap(), x_t.next().unwrap())
         })
         .collect::<Vec<_>>();
@@ -55,7 +49,7 @@ pub fn part_two(input: &str) -> Option<u32> {
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 4);
+    let input = &advent_of_code::read_input(4);
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
@@ -12,7 +12,7 @@ fn get_stacks(stack_raw: &str) -> HashMap<u16, Vec<char>> {
     let mut stack_iter = stack_raw.lines().rev().filter(|x| !x.is_empty());
     let first_line = stack_iter.next().unwrap();
     for c in first_line.chars() {
-        if c.is_digit(10) {
+        if c.is_ascii_digit() {
             stacks.insert(c.to_digit(10).unwrap() as u16, Vec::new());
         }
     }
@@ -20,7 +20,7 @@ fn get_stacks(stack_raw: &str) -> HashMap<u16, Vec<char>> {
         for (i, c) in line.chars().enumerate() {
             if c.is_alphabetic() {
                 let column = first_line.chars().nth(i).unwrap().to_digit(10).unwrap() as u16;
-                stacks.get_mut(&column).unwrap().push(c.clone());
+                stacks.get_mut(&column).unwrap().push(c);
             }
         }
     }
@@ -90,7 +90,7 @@ pub fn part_two(input: &str) -> Option<String> {
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 5);
+    let input = &advent_of_code::read_input(5);
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
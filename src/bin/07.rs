@@ -1,5 +1,3 @@
-use std::ops::Add;
-
 #[derive(Debug)]
 struct Obj {
     name: String,
@@ -8,7 +6,7 @@ struct Obj {
 }
 
 #[derive(Debug)]
-struct Arena {
+pub struct Arena {
     nodes: Vec<Node>,
 }
 
@@ -65,29 +63,6 @@ impl Arena {
         self.nodes.get(id.index).unwrap().parent
     }
 
-    fn format_node(&self, id: NodeId, depth: usize) -> String {
-        let children = self
-            .get_children(id)
-            .iter()
-            .map(|x| self.format_node(**x, depth + 1))
-            .collect::<Vec<String>>()
-            .join("\n")
-            .split('\n')
-            .map(|x| format!("{}{}{}", "\n", "\t".repeat(depth), x))
-            .collect::<String>();
-        let children_str = format!("{}", children);
-        format!(
-            "Node(id:{}, data:{:#?}, children:{})",
-            id.index,
-            self.get(id),
-            children_str
-        )
-    }
-
-    fn print(&self, id: NodeId) {
-        print!("{}", self.format_node(id, 0))
-    }
-
     fn get_size(&self, id: NodeId) -> usize {
         let node = self.get(id);
         if node.is_dir {
@@ -100,7 +75,7 @@ impl Arena {
         }
     }
     fn get_ids(&self) -> Vec<NodeId> {
-        (0..self.nodes.len()).map(|x| NodeId::new(x)).collect()
+        (0..self.nodes.len()).map(NodeId::new).collect()
     }
 }
 
@@ -171,56 +146,66 @@ fn parse_instructions(input: &str) -> Arena {
     arena
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let tree = parse_instructions(input);
-    let mut size = 0;
-    for id in tree.get_ids() {
-        let node = tree.get(id);
-        if node.is_dir {
-            let node_size = tree.get_size(id);
-            if node_size < 100000 {
-                size += node_size;
+pub struct Day07;
+
+impl advent_of_code::Solution for Day07 {
+    type Parsed = Arena;
+    type PartOne = Option<usize>;
+    type PartTwo = Option<usize>;
+
+    fn parse(input: &str) -> Arena {
+        parse_instructions(input)
+    }
+
+    fn part_one(tree: &Arena) -> Option<usize> {
+        let mut size = 0;
+        for id in tree.get_ids() {
+            let node = tree.get(id);
+            if node.is_dir {
+                let node_size = tree.get_size(id);
+                if node_size < 100000 {
+                    size += node_size;
+                }
             }
         }
+        Some(size)
     }
-    Some(size)
-}
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let tree = parse_instructions(input);
-    let total_space: usize = 70000000;
-    let update_space: usize = 30000000;
-    let used_space = tree.get_size(NodeId::new(0));
-    Some(
-        tree.get_ids()
-            .iter()
-            .filter(|x| tree.get(**x).is_dir)
-            .map(|x| tree.get_size(*x))
-            .filter(|size| (total_space - (used_space - size)) >= update_space)
-            .min()
-            .unwrap(),
-    )
+    fn part_two(tree: &Arena) -> Option<usize> {
+        let total_space: usize = 70000000;
+        let update_space: usize = 30000000;
+        let used_space = tree.get_size(NodeId::new(0));
+        Some(
+            tree.get_ids()
+                .iter()
+                .filter(|x| tree.get(**x).is_dir)
+                .map(|x| tree.get_size(*x))
+                .filter(|size| (total_space - (used_space - size)) >= update_space)
+                .min()
+                .unwrap(),
+        )
+    }
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 7);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
+    let input = &advent_of_code::read_input(7);
+    advent_of_code::solve!(Day07, input);
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::Solution;
 
     #[test]
     fn test_part_one() {
         let input = advent_of_code::read_file("examples", 7);
-        assert_eq!(part_one(&input), Some(95437));
+        assert_eq!(Day07::part_one(&Day07::parse(&input)), Some(95437));
     }
 
     #[test]
     fn test_part_two() {
         let input = advent_of_code::read_file("examples", 7);
-        assert_eq!(part_two(&input), Some(24933642));
+        assert_eq!(Day07::part_two(&Day07::parse(&input)), Some(24933642));
     }
 }
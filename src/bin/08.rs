@@ -1,5 +1,3 @@
-use std::borrow::BorrowMut;
-
 #[derive(Debug)]
 struct Tree {
     height: i32,
@@ -95,13 +93,13 @@ impl TreeMap {
     }
 
     fn check(&mut self, row_reverse: bool, column_reverse: bool) {
-        let mut height_view = (0..self.map.get(0).unwrap().len())
+        let mut height_view = (0..self.map.first().unwrap().len())
             .map(|_| -1)
             .collect::<Vec<i32>>();
         let mut row_iter: Vec<Vec<&mut Tree>> = self
             .map
             .iter_mut()
-            .map(|x| x.into_iter().collect())
+            .map(|x| x.iter_mut().collect())
             .collect();
         if column_reverse {
             row_iter = transpose2(row_iter);
@@ -110,13 +108,11 @@ impl TreeMap {
             row_iter = row_iter.into_iter().rev().collect();
         }
         for row in row_iter {
-            let mut i = 0;
-            for mut item in row {
+            for (i, item) in row.into_iter().enumerate() {
                 if item.height > height_view[i] {
                     item.visible = true;
                     height_view[i] = item.height;
                 }
-                i += 1;
             }
         }
     }
@@ -233,7 +229,7 @@ pub fn part_two(input: &str) -> Option<u32> {
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 8);
+    let input = &advent_of_code::read_input(8);
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
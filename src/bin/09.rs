@@ -1,8 +1,4 @@
-use std::{
-    cmp::{max, min},
-    collections::HashSet,
-    fmt::Debug,
-};
+use std::{collections::HashSet, fmt::Debug};
 
 #[derive(Debug, Clone, Copy)]
 enum Direction {
@@ -41,15 +37,12 @@ fn parse_input(input: &str) -> Vec<Motion> {
 
 struct Board {
     visited: HashSet<(i32, i32)>,
-    width_range: (i32, i32),
-    height_range: (i32, i32),
-    starting_point: (i32, i32),
     head: (i32, i32),
     tails: Vec<(i32, i32)>,
 }
 
 fn clip_int(v: i32) -> i32 {
-    min(max(v, -1), 1)
+    v.clamp(-1, 1)
 }
 
 impl Board {
@@ -59,9 +52,6 @@ impl Board {
         visited.insert(starting_point);
         Board {
             visited,
-            starting_point,
-            width_range: (min(0, starting_point.0), max(0, starting_point.0)),
-            height_range: (min(0, starting_point.1), max(0, starting_point.1)),
             head: starting_point,
             tails: (0..tails).map(|_| starting_point).collect(),
         }
@@ -75,14 +65,6 @@ impl Board {
                 Direction::Left => (self.head.0, self.head.1 - 1),
                 Direction::Right => (self.head.0, self.head.1 + 1),
             };
-            self.height_range = (
-                min(self.height_range.0, self.head.0),
-                max(self.height_range.1, self.head.0),
-            );
-            self.width_range = (
-                min(self.width_range.0, self.head.1),
-                max(self.width_range.1, self.head.1),
-            );
             let mut current_head = self.head;
             for i in 0..self.tails.len() {
                 self.tails[i] = self.refresh_tail(current_head, self.tails[i]);
@@ -98,41 +80,7 @@ impl Board {
             return tail;
         }
         let diff_clipped = (clip_int(diff.0), clip_int(diff.1));
-        let new_tail = (tail.0 + diff_clipped.0, tail.1 + diff_clipped.1);
-        new_tail
-    }
-
-    fn print_board(&self) {
-        let mut board: Vec<Vec<String>> = (self.height_range.0..self.height_range.1 + 1)
-            .map(|_| {
-                (self.width_range.0..self.width_range.1 + 1)
-                    .map(|_| ".".to_string())
-                    .collect::<Vec<_>>()
-            })
-            .collect::<Vec<_>>();
-        for (x, y) in self.visited.iter() {
-            board[(x - self.height_range.0) as usize][(y - self.width_range.0) as usize] =
-                "#".to_string();
-        }
-        board[(self.starting_point.0 - self.height_range.0) as usize]
-            [(self.starting_point.0 - self.width_range.0) as usize] = "s".to_string();
-        for i in 0..self.tails.len() {
-            let i_fixed = i + 1;
-            let tail_str = format!("{}", i_fixed);
-            board[(self.tails[i].0 - self.height_range.0) as usize]
-                [(self.tails[i].1 - self.width_range.0) as usize] = tail_str;
-        }
-        board[(self.head.0 - self.height_range.0) as usize]
-            [(self.head.1 - self.width_range.0) as usize] = "H".to_string();
-        println!(
-            "{}",
-            board
-                .iter()
-                .rev()
-                .map(|x| x.join(""))
-                .collect::<Vec<String>>()
-                .join("\n")
-        );
+        (tail.0 + diff_clipped.0, tail.1 + diff_clipped.1)
     }
 
     fn get_len_visited(&self) -> usize {
@@ -159,7 +107,7 @@ pub fn part_two(input: &str) -> Option<usize> {
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 9);
+    let input = &advent_of_code::read_input(9);
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
@@ -170,13 +118,11 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 9);
-        assert_eq!(part_one(&input), Some(88));
+        advent_of_code::examples::check(9, 1, part_one);
     }
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 9);
-        assert_eq!(part_two(&input), Some(36));
+        advent_of_code::examples::check(9, 2, part_two);
     }
 }
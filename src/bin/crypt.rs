@@ -0,0 +1,149 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use advent_of_code::crypt::{self, CryptError, Key};
+use std::{env, path::Path, process};
+
+enum Command {
+    Keygen { force: bool },
+    Encrypt,
+    Decrypt { force: bool },
+    Rotate,
+}
+
+fn parse_args() -> Result<Command, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    let command = match args.subcommand()?.as_deref() {
+        Some("keygen") => Command::Keygen {
+            force: args.contains("--force"),
+        },
+        Some("encrypt") => Command::Encrypt,
+        Some("decrypt") => Command::Decrypt {
+            force: args.contains("--force"),
+        },
+        Some("rotate") => Command::Rotate,
+        other => {
+            return Err(pico_args::Error::ArgumentParsingFailed {
+                cause: format!(
+                    "unknown command {:?}, use keygen, encrypt, decrypt or rotate",
+                    other.unwrap_or_default()
+                ),
+            })
+        }
+    };
+    advent_of_code::args::finish(args)?;
+    Ok(command)
+}
+
+fn require_key() -> Result<Key, CryptError> {
+    crypt::load_key()?.ok_or(CryptError::KeyNotFound)
+}
+
+fn display(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
+
+fn keygen(force: bool) -> Result<(), CryptError> {
+    if Path::new(crypt::KEY_FILE).exists() && !force {
+        eprintln!(
+            "\"{}\" exists already. Pass --force to replace it, or `cargo crypt rotate` to re-encrypt with a new key.",
+            crypt::KEY_FILE
+        );
+        process::exit(1);
+    }
+
+    let key = Key::generate();
+    key.save(Path::new(crypt::KEY_FILE))?;
+    println!("🔑 Wrote a new key to \"{}\".", crypt::KEY_FILE);
+    println!(
+        "Share it with your team and store it as the {} secret of your CI workflow.",
+        crypt::KEY_ENV
+    );
+    Ok(())
+}
+
+fn encrypt(root: &Path) -> Result<(), CryptError> {
+    let key = require_key()?;
+    for path in crypt::files(root, false)? {
+        if crypt::encrypt_file(&key, root, &path)? {
+            println!(
+                "🔒 Encrypted \"{}\".",
+                display(root, &crypt::encrypted_path(&path))
+            );
+        }
+    }
+    Ok(())
+}
+
+fn decrypt(root: &Path, force: bool) -> Result<(), CryptError> {
+    let key = require_key()?;
+    for encrypted_path in crypt::files(root, true)? {
+        match crypt::decrypt_file(&key, root, &encrypted_path, force)? {
+            Some(path) => println!("🔓 Decrypted \"{}\".", display(root, &path)),
+            None => println!(
+                "Kept \"{}\", pass --force to replace it.",
+                display(root, &encrypted_path.with_extension(""))
+            ),
+        }
+    }
+    Ok(())
+}
+
+/// Re-encrypts every file with a new key, which replaces the key file.
+fn rotate(root: &Path) -> Result<(), CryptError> {
+    let old = require_key()?;
+    let new = Key::generate();
+    let files = crypt::files(root, true)?;
+
+    // check every file before changing any of them.
+    for path in &files {
+        crypt::read_encrypted(&old, root, path)?;
+    }
+    for path in &files {
+        crypt::rotate_file(&old, &new, root, path)?;
+        println!("🔒 Re-encrypted \"{}\".", display(root, path));
+    }
+
+    if let Err(e) = new.save(Path::new(crypt::KEY_FILE)) {
+        eprintln!(
+            "could not write the new key, the files can only be decrypted with {}",
+            new.to_hex()
+        );
+        return Err(e.into());
+    }
+    println!("🔑 Wrote the new key to \"{}\".", crypt::KEY_FILE);
+    if env::var_os(crypt::KEY_ENV).is_some() {
+        println!(
+            "{} is set and takes precedence over the key file. Update it, and the secret of your CI workflow, to the new key.",
+            crypt::KEY_ENV
+        );
+    }
+    Ok(())
+}
+
+fn main() {
+    let command = match parse_args() {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {}", e);
+            process::exit(1);
+        }
+    };
+
+    let root = env::current_dir().unwrap_or_default();
+    let result = match command {
+        Command::Keygen { force } => keygen(force),
+        Command::Encrypt => encrypt(&root),
+        Command::Decrypt { force } => decrypt(&root, force),
+        Command::Rotate => rotate(&root),
+    };
+
+    if let Err(e) = result {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
+}
@@ -2,20 +2,49 @@
  * This file contains template code.
  * There is no need to edit this file unless you want to change template functionality.
  */
-use advent_of_code::aoc_cli;
-use std::process;
+use advent_of_code::{
+    aoc_cli::{self, AocCliError},
+    aoc_client::{AocClient, AocError},
+    args, examples, profiles,
+    puzzle::{self, Puzzle},
+    unlock::{self, Clock, SystemClock},
+};
+use std::{
+    fs,
+    io::{self, Write},
+    path::Path,
+    process::{self, Command},
+    time::Duration,
+};
 
 struct Args {
     day: u8,
     year: Option<u16>,
+    profile: Option<String>,
+    pick: Option<usize>,
+    wait: bool,
+    scaffold: bool,
 }
 
 fn parse_args() -> Result<Args, pico_args::Error> {
     let mut args = pico_args::Arguments::from_env();
-    Ok(Args {
+    // options come first, the day is whatever argument is left.
+    let year = args.opt_value_from_fn(["-y", "--year"], puzzle::parse_year)?;
+    let profile = args.opt_value_from_str("--profile")?;
+    let pick = args.opt_value_from_str("--pick")?;
+    let wait = args.contains("--wait");
+    let scaffold = args.contains("--scaffold");
+    args::reject_options(&args)?;
+    let parsed = Args {
         day: args.free_from_str()?,
-        year: args.opt_value_from_str(["-y", "--year"])?,
-    })
+        year,
+        profile,
+        pick,
+        wait,
+        scaffold,
+    };
+    args::finish(args)?;
+    Ok(parsed)
 }
 
 fn main() {
@@ -27,20 +56,154 @@ fn main() {
         }
     };
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+    let year = puzzle::year_or_default(args.year).unwrap_or_else(|e| {
+        eprintln!("Failed to process arguments: {}", e);
+        process::exit(1);
+    });
+
+    if let Err(e) = profiles::activate(args.profile.clone()) {
+        eprintln!("Failed to process arguments: {}", e);
         process::exit(1);
     }
 
-    match aoc_cli::download(args.day, args.year) {
-        Ok(cmd_output) => {
-            if !cmd_output.status.success() {
-                process::exit(1);
-            }
+    let puzzle = Puzzle::new(year, args.day);
+    let clock = SystemClock;
+
+    // check the session before waiting, not once the puzzle unlocked.
+    let client = match AocClient::from_env() {
+        Ok(client) => Some(client),
+        Err(AocError::SessionNotFound) if aoc_cli::check().is_ok() => None,
+        Err(e) => {
+            eprintln!("failed to download {}: {}", puzzle, e);
+            process::exit(1);
+        }
+    };
+
+    // right after the unlock, the puzzle might not be served yet: retry for a bit.
+    let backoff: &[Duration] = if args.wait {
+        wait_for_unlock(&clock, puzzle);
+        &unlock::BACKOFF
+    } else {
+        &[]
+    };
+
+    match client {
+        Some(client) => download(&clock, backoff, &client, puzzle),
+        None => download_with_aoc_cli(&clock, backoff, puzzle),
+    }
+    extract_example(puzzle, args.pick);
+
+    if args.scaffold {
+        scaffold(puzzle);
+    }
+}
+
+/// Shows a countdown until the puzzle unlocks.
+fn wait_for_unlock(clock: &impl Clock, puzzle: Puzzle) {
+    let year = AocClient::event_year(puzzle);
+    let unlock = puzzle::unlock_time(year, puzzle.day);
+    if unlock::remaining(clock, unlock).is_zero() {
+        return;
+    }
+
+    let puzzle = Puzzle::from((year, puzzle.day));
+    println!("⏳ Waiting for {} to unlock.", puzzle);
+    unlock::wait_until(clock, unlock, |left| {
+        print!("\r⏳ Unlocks in {} ", unlock::format_countdown(left));
+        let _ = io::stdout().flush();
+    });
+    println!("\r🔓 Unlocked!{}", " ".repeat(20));
+}
+
+fn download(clock: &impl Clock, backoff: &[Duration], client: &AocClient, puzzle: Puzzle) {
+    let retryable = |e: &AocError| {
+        matches!(
+            e,
+            AocError::NotFound(_) | AocError::Transport(_) | AocError::BadStatus(500..)
+        )
+    };
+
+    match unlock::retry(clock, backoff, || client.download(puzzle), retryable) {
+        Ok((input_path, puzzle_path)) => {
+            println!(
+                "🎄 Successfully wrote input to \"{}\".",
+                input_path.display()
+            );
+            println!(
+                "🎄 Successfully wrote puzzle to \"{}\".",
+                puzzle_path.display()
+            );
         }
         Err(e) => {
-            eprintln!("failed to spawn aoc-cli: {}", e);
+            eprintln!("failed to download {}: {}", puzzle, e);
             process::exit(1);
         }
     }
 }
+
+/// Creates the module of the day with `cargo scaffold`, unless it exists already.
+fn scaffold(puzzle: Puzzle) {
+    if Path::new(&format!("src/bin/{}.rs", puzzle.bin_name())).exists() {
+        return;
+    }
+
+    let mut cmd = Command::new("cargo");
+    cmd.args(["scaffold", &puzzle.day.to_string()]);
+    if let Some(year) = puzzle.year {
+        cmd.args(["--year", &year.to_string()]);
+    }
+
+    match cmd.status() {
+        Ok(status) if status.success() => {}
+        Ok(_) => process::exit(1),
+        Err(e) => {
+            eprintln!("failed to scaffold {}: {}", puzzle, e);
+            process::exit(1);
+        }
+    }
+}
+
+/// Copies the example of the downloaded description to `src/examples`
+/// and fills in the expected answers of the day's scaffolded tests.
+fn extract_example(puzzle: Puzzle, pick: Option<usize>) {
+    let extracted = match examples::extract(puzzle, pick) {
+        Ok(extracted) => extracted,
+        Err(e) => {
+            eprintln!("could not extract the example: {}", e);
+            return;
+        }
+    };
+
+    // an explicitly picked example replaces the one written before.
+    match examples::save(puzzle, &extracted, pick.is_some()) {
+        Ok(written) => {
+            for path in written {
+                println!("🎄 Extracted example to \"{}\".", path.display());
+            }
+        }
+        Err(e) => eprintln!("could not write the example: {}", e),
+    }
+
+    let module_path = format!("src/bin/{}.rs", puzzle.bin_name());
+    if let Ok(module) = fs::read_to_string(&module_path) {
+        let filled = examples::fill_assertions(&module, &extracted.answers);
+        if filled != module {
+            match fs::write(&module_path, filled) {
+                Ok(_) => println!("🎄 Filled in example answers in \"{}\".", module_path),
+                Err(e) => eprintln!("could not fill in example answers: {}", e),
+            }
+        }
+    }
+}
+
+/// Falls back to an installed aoc-cli, which reads the session from its own config.
+fn download_with_aoc_cli(clock: &impl Clock, backoff: &[Duration], puzzle: Puzzle) {
+    let retryable =
+        |e: &AocCliError| matches!(e, AocCliError::PuzzleLocked | AocCliError::RateLimited);
+
+    // aoc-cli already printed its output, failures are explained below it.
+    if let Err(e) = unlock::retry(clock, backoff, || aoc_cli::download(puzzle), retryable) {
+        eprintln!("failed to download {} with aoc-cli: {}", puzzle, e);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,111 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use advent_of_code::profiles::{self, ProfileError, Profiles};
+use std::{
+    io::{self, Write},
+    process,
+};
+
+enum Command {
+    Login {
+        name: String,
+        session: Option<String>,
+    },
+    List,
+    Remove {
+        name: String,
+    },
+}
+
+fn parse_args() -> Result<Command, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    let command = match args.subcommand()?.as_deref() {
+        Some("login") => Command::Login {
+            name: args.free_from_str()?,
+            session: args.opt_free_from_str()?,
+        },
+        Some("list") | None => Command::List,
+        Some("remove") => Command::Remove {
+            name: args.free_from_str()?,
+        },
+        Some(other) => {
+            return Err(pico_args::Error::ArgumentParsingFailed {
+                cause: format!("unknown command \"{}\", use login, list or remove", other),
+            })
+        }
+    };
+    advent_of_code::args::finish(args)?;
+    Ok(command)
+}
+
+/// Asks for the cookie, so that it does not end up in the shell history.
+fn prompt_session(name: &str) -> io::Result<String> {
+    print!("Paste the session cookie of \"{}\": ", name);
+    io::stdout().flush()?;
+    let mut session = String::new();
+    io::stdin().read_line(&mut session)?;
+    Ok(session)
+}
+
+fn run(command: Command) -> Result<(), ProfileError> {
+    let profiles = Profiles::from_home()?;
+
+    match command {
+        Command::Login { name, session } => {
+            let name = profiles::parse_name(&name)?;
+            let session = match session {
+                Some(session) => session,
+                None => prompt_session(&name)?,
+            };
+            let path = profiles.save(&name, &session)?;
+            println!(
+                "🎄 Stored the session cookie of \"{}\" in \"{}\".",
+                name,
+                path.display()
+            );
+            println!(
+                "Pass `--profile {}` or set {}={} to use it.",
+                name,
+                profiles::PROFILE_ENV,
+                name
+            );
+        }
+        Command::List => {
+            let names = profiles.list()?;
+            if names.is_empty() {
+                println!("No profiles yet. Add one with `cargo profile login <name>`.");
+            }
+            for name in names {
+                let marker = if profiles::active() == Some(name.as_str()) {
+                    "*"
+                } else {
+                    " "
+                };
+                println!("{} {}", marker, name);
+            }
+        }
+        Command::Remove { name } => {
+            profiles.remove(&name)?;
+            println!("Removed profile \"{}\".", name);
+        }
+    }
+
+    Ok(())
+}
+
+fn main() {
+    let command = match parse_args() {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {}", e);
+            process::exit(1);
+        }
+    };
+
+    if let Err(e) = run(command) {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
+}
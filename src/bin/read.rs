@@ -2,20 +2,36 @@
  * This file contains template code.
  * There is no need to edit this file unless you want to change template functionality.
  */
-use advent_of_code::aoc_cli;
-use std::process;
+use advent_of_code::{
+    aoc_cli,
+    aoc_client::{AocClient, AocError},
+    args, markdown, profiles,
+    puzzle::{self, Puzzle},
+};
+use std::{fs, process};
 
 struct Args {
     day: u8,
     year: Option<u16>,
+    profile: Option<String>,
+    refresh: bool,
 }
 
 fn parse_args() -> Result<Args, pico_args::Error> {
     let mut args = pico_args::Arguments::from_env();
-    Ok(Args {
+    // options come first, the day is whatever argument is left.
+    let year = args.opt_value_from_fn(["-y", "--year"], puzzle::parse_year)?;
+    let profile = args.opt_value_from_str("--profile")?;
+    let refresh = args.contains("--refresh");
+    args::reject_options(&args)?;
+    let parsed = Args {
         day: args.free_from_str()?,
-        year: args.opt_value_from_str(["-y", "--year"])?,
-    })
+        year,
+        profile,
+        refresh,
+    };
+    args::finish(args)?;
+    Ok(parsed)
 }
 
 fn main() {
@@ -27,20 +43,72 @@ fn main() {
         }
     };
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+    let year = puzzle::year_or_default(args.year).unwrap_or_else(|e| {
+        eprintln!("Failed to process arguments: {}", e);
+        process::exit(1);
+    });
+
+    if let Err(e) = profiles::activate(args.profile.clone()) {
+        eprintln!("Failed to process arguments: {}", e);
         process::exit(1);
     }
 
-    match aoc_cli::read(args.day, args.year) {
-        Ok(cmd_output) => {
-            if !cmd_output.status.success() {
-                process::exit(1);
-            }
+    let puzzle = Puzzle::new(year, args.day);
+    let path = puzzle.relative_path("puzzles", "md");
+
+    let description = match fs::read_to_string(&path) {
+        Ok(description) if !args.refresh => description,
+        _ => fetch(puzzle),
+    };
+
+    let rendered = markdown::render(
+        &description,
+        markdown::terminal_width(),
+        markdown::use_color(),
+    );
+    markdown::page(&rendered);
+}
+
+/// Downloads the description and caches it in `src/puzzles`.
+fn fetch(puzzle: Puzzle) -> String {
+    let client = match AocClient::from_env() {
+        Ok(client) => client,
+        Err(AocError::SessionNotFound) if aoc_cli::check().is_ok() => {
+            read_with_aoc_cli(puzzle);
+            process::exit(0);
         }
         Err(e) => {
-            eprintln!("failed to spawn aoc-cli: {}", e);
+            eprintln!("failed to read {}: {}", puzzle, e);
             process::exit(1);
         }
+    };
+
+    let description = client.get_puzzle(puzzle).unwrap_or_else(|e| {
+        eprintln!("failed to read {}: {}", puzzle, e);
+        process::exit(1);
+    });
+
+    let path = puzzle.relative_path("puzzles", "md");
+    let saved = match path.parent() {
+        Some(dir) => fs::create_dir_all(dir).and_then(|_| fs::write(&path, &description)),
+        None => fs::write(&path, &description),
+    };
+    if let Err(e) = saved {
+        eprintln!(
+            "could not cache the puzzle in \"{}\": {}",
+            path.display(),
+            e
+        );
+    }
+
+    description
+}
+
+/// Falls back to an installed aoc-cli, which reads the session from its own config.
+fn read_with_aoc_cli(puzzle: Puzzle) {
+    // aoc-cli already printed its output, failures are explained below it.
+    if let Err(e) = aoc_cli::read(puzzle) {
+        eprintln!("failed to read {} with aoc-cli: {}", puzzle, e);
+        process::exit(1);
     }
 }
@@ -2,71 +2,211 @@
  * This file contains template code.
  * There is no need to edit this file unless you want to change template functionality.
  */
+use advent_of_code::{
+    args, examples,
+    puzzle::{self, Puzzle},
+    templates::{self, Placeholders},
+};
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
-const MODULE_TEMPLATE: &str = r###"pub fn part_one(input: &str) -> Option<u32> {
-    None
+const REGISTRY_PATH: &str = "src/days.rs";
+const REGISTRY_MACRO: &str = "register_days! {";
+
+struct Args {
+    day: u8,
+    year: Option<u16>,
+    template: String,
+    answer_type: String,
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    None
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    let result = args.contains("--result");
+    let template: Option<String> = args.opt_value_from_str("--template")?;
+    if result && template.is_some() {
+        return Err(pico_args::Error::ArgumentParsingFailed {
+            cause: "--result is a shorthand for --template result, pass only one of them".into(),
+        });
+    }
+
+    let answer_type = args
+        .opt_value_from_str("--answer-type")?
+        .unwrap_or_else(|| templates::DEFAULT_ANSWER_TYPE.into());
+    let year = args.opt_value_from_fn(["-y", "--year"], puzzle::parse_year)?;
+    args::reject_options(&args)?;
+
+    let parsed = Args {
+        template: match result {
+            true => "result".into(),
+            false => template.unwrap_or_else(|| templates::DEFAULT_TEMPLATE.into()),
+        },
+        answer_type,
+        year,
+        day: args.free_from_str()?,
+    };
+    args::finish(args)?;
+    Ok(parsed)
 }
 
-fn main() {
-    let input = &advent_of_code::read_file("inputs", DAY);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
+fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
+    create_parent_dir(path)?;
+    OpenOptions::new().write(true).create_new(true).open(path)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+fn create_file(path: &str) -> Result<File, std::io::Error> {
+    create_parent_dir(path)?;
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
+}
 
-    #[test]
-    fn test_part_one() {
-        let input = advent_of_code::read_file("examples", DAY);
-        assert_eq!(part_one(&input), None);
+/// Days of a year are kept in a directory per year, which may not exist yet.
+fn create_parent_dir(path: &str) -> Result<(), std::io::Error> {
+    match Path::new(path).parent() {
+        Some(dir) => fs::create_dir_all(dir),
+        None => Ok(()),
     }
+}
 
-    #[test]
-    fn test_part_two() {
-        let input = advent_of_code::read_file("examples", DAY);
-        assert_eq!(part_two(&input), None);
+/// Parses the `N` or `YYYY, N` in front of a registry entry.
+fn parse_registry_key(key: &str) -> Option<Puzzle> {
+    match key.split_once(',') {
+        Some((year, day)) => Some(Puzzle::new(
+            Some(year.trim().parse().ok()?),
+            day.trim().parse().ok()?,
+        )),
+        None => Some(Puzzle::new(None, key.trim().parse().ok()?)),
     }
 }
-"###;
 
-fn parse_args() -> Result<u8, pico_args::Error> {
-    let mut args = pico_args::Arguments::from_env();
-    args.free_from_str()
+/// Entry of a day, e.g. `7 => "bin/07.rs" as day07,`. Days implementing `Solution` are
+/// registered with their type, e.g. `7 => "bin/07.rs" as day07: Day07,`.
+fn registry_entry(puzzle: Puzzle, solution: Option<&str>) -> String {
+    let solution = solution.map(|s| format!(": {}", s)).unwrap_or_default();
+    match puzzle.year {
+        Some(year) => format!(
+            "    {}, {} => \"bin/{}.rs\" as y{}_day{:02}{},",
+            year,
+            puzzle.day,
+            puzzle.bin_name(),
+            year,
+            puzzle.day,
+            solution
+        ),
+        None => format!(
+            "    {} => \"bin/{}.rs\" as day{:02}{},",
+            puzzle.day,
+            puzzle.bin_name(),
+            puzzle.day,
+            solution
+        ),
+    }
 }
 
-fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create_new(true).open(path)
-}
+/// Adds a day to the `register_days!` invocation in `src/days.rs`, keeping entries sorted.
+fn register_day(puzzle: Puzzle, solution: Option<&str>) -> Result<(), String> {
+    let registry = fs::read_to_string(REGISTRY_PATH).map_err(|e| e.to_string())?;
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+    let start = registry
+        .find(REGISTRY_MACRO)
+        .ok_or("could not find day registry")?
+        + REGISTRY_MACRO.len();
+    let end = start
+        + registry[start..]
+            .find("\n}")
+            .ok_or("could not find end of day registry")?;
+
+    let mut entries: Vec<(Puzzle, String)> = registry[start..end]
+        .lines()
+        .filter_map(|line| {
+            let puzzle = parse_registry_key(line.split("=>").next()?)?;
+            Some((puzzle, line.to_string()))
+        })
+        .collect();
+
+    if entries.iter().any(|(p, _)| *p == puzzle) {
+        return Ok(());
+    }
+
+    entries.push((puzzle, registry_entry(puzzle, solution)));
+    entries.sort_by_key(|(p, _)| *p);
+
+    let body = entries
+        .into_iter()
+        .map(|(_, line)| format!("\n{}", line))
+        .collect::<String>();
+
+    let updated = format!("{}{}{}", &registry[..start], body, &registry[end..]);
+    fs::write(REGISTRY_PATH, updated).map_err(|e| e.to_string())
 }
 
 fn main() {
-    let day = match parse_args() {
-        Ok(day) => day,
-        Err(_) => {
+    let Args {
+        day,
+        year,
+        template,
+        answer_type,
+    } = match parse_args() {
+        Ok(args) => args,
+        Err(pico_args::Error::MissingArgument)
+        | Err(pico_args::Error::Utf8ArgumentParsingFailed { .. }) => {
             eprintln!("Need to specify a day (as integer). example: `cargo scaffold 7`");
             process::exit(1);
         }
+        Err(e) => {
+            eprintln!("Failed to process arguments: {}", e);
+            process::exit(1);
+        }
     };
 
-    let day_padded = format!("{:02}", day);
+    let year = puzzle::year_or_default(year).unwrap_or_else(|e| {
+        eprintln!("Failed to process arguments: {}", e);
+        process::exit(1);
+    });
+    let puzzle = Puzzle::new(year, day);
+
+    let path = |folder, extension| {
+        puzzle
+            .relative_path(folder, extension)
+            .display()
+            .to_string()
+    };
+    let input_path = path("inputs", "txt");
+    let example_path = path("examples", "txt");
+    let module_path = format!("src/bin/{}.rs", puzzle.bin_name());
 
-    let input_path = format!("src/inputs/{}.txt", day_padded);
-    let example_path = format!("src/examples/{}.txt", day_padded);
-    let module_path = format!("src/bin/{}.rs", day_padded);
+    let template = match templates::load(&template) {
+        Ok(template) => template,
+        Err(e) => {
+            eprintln!("Failed to load template: {}", e);
+            process::exit(1);
+        }
+    };
+
+    // the description is already there if the day was downloaded before it was scaffolded.
+    let description = fs::read_to_string(puzzle.relative_path("puzzles", "md")).ok();
+    let placeholders = Placeholders {
+        puzzle,
+        title: description.as_deref().and_then(templates::title),
+        answer_type,
+    };
+    let mut module = placeholders.render(&template);
+
+    // checked before anything is written, an unusable registry entry breaks the whole crate.
+    let solution = match templates::solution_type(&module) {
+        Ok(solution) => solution,
+        Err(e) => {
+            eprintln!("Failed to load template: {}", e);
+            process::exit(1);
+        }
+    };
 
     let mut file = match safe_create_file(&module_path) {
         Ok(file) => file,
@@ -76,7 +216,12 @@ fn main() {
         }
     };
 
-    match file.write_all(MODULE_TEMPLATE.replace("DAY", &day.to_string()).as_bytes()) {
+    let extracted = description.and_then(|_| examples::extract(puzzle, None).ok());
+    if let Some(extracted) = &extracted {
+        module = examples::fill_assertions(&module, &extracted.answers);
+    }
+
+    match file.write_all(module.as_bytes()) {
         Ok(_) => {
             println!("Created module file \"{}\"", &module_path);
         }
@@ -86,6 +231,16 @@ fn main() {
         }
     }
 
+    match register_day(puzzle, solution.as_deref()) {
+        Ok(_) => {
+            println!("Registered day in \"{}\"", REGISTRY_PATH);
+        }
+        Err(e) => {
+            eprintln!("Failed to register day: {}", e);
+            process::exit(1);
+        }
+    }
+
     match create_file(&input_path) {
         Ok(_) => {
             println!("Created empty input file \"{}\"", &input_path);
@@ -106,9 +261,20 @@ fn main() {
         }
     }
 
+    if let Some(extracted) = extracted {
+        match examples::save(puzzle, &extracted, false) {
+            Ok(written) => {
+                for path in written {
+                    println!("Extracted example to \"{}\"", path.display());
+                }
+            }
+            Err(e) => eprintln!("Failed to extract example: {}", e),
+        }
+    }
+
     println!("---");
     println!(
         "🎄 Type `cargo solve {}` to run your solution.",
-        &day_padded
+        puzzle.bin_name()
     );
 }
@@ -0,0 +1,146 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use advent_of_code::{
+    answers::Answers,
+    aoc_cli,
+    aoc_client::{AocClient, AocError},
+    args::RunArgs,
+    days, profiles,
+    puzzle::{self, Puzzle},
+    runner,
+    submit::{self, AocCli, Ledger, Response, SubmitBackend},
+};
+use std::{process, time::SystemTime};
+
+struct Args {
+    day: u8,
+    part: u8,
+    answer: Option<String>,
+    year: Option<u16>,
+    profile: Option<String>,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    let year = args.opt_value_from_fn(["-y", "--year"], puzzle::parse_year)?;
+    let profile = args.opt_value_from_str("--profile")?;
+    advent_of_code::args::reject_options(&args)?;
+    let parsed = Args {
+        day: args.free_from_str()?,
+        part: args.free_from_fn(|val| match val {
+            "1" => Ok(1),
+            "2" => Ok(2),
+            _ => Err("part must be 1 or 2"),
+        })?,
+        answer: args.opt_free_from_str()?,
+        year,
+        profile,
+    };
+    advent_of_code::args::finish(args)?;
+    Ok(parsed)
+}
+
+/// Runs the day's part against its input and returns the answer.
+fn compute_answer(puzzle: Puzzle, part: u8) -> Option<String> {
+    let day = match days::get(puzzle) {
+        Some(day) => day,
+        None => {
+            eprintln!(
+                "{} is not registered in \"src/days.rs\". Pass the answer to submit instead.",
+                puzzle
+            );
+            return None;
+        }
+    };
+
+    let args = RunArgs {
+        part: Some(part),
+        ..RunArgs::default()
+    };
+    let result = runner::run_day(day, &args);
+    if let Some(reason) = result.skipped {
+        eprintln!("could not run {}: {}", puzzle, reason);
+        return None;
+    }
+    let answer = result
+        .parts
+        .iter()
+        .find(|r| r.part == part)
+        .and_then(|r| r.answer());
+
+    if answer.is_none() {
+        eprintln!("part {} of {} has no answer to submit.", part, puzzle);
+    }
+    answer.map(String::from)
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {}", e);
+            process::exit(1);
+        }
+    };
+
+    let year = puzzle::year_or_default(args.year).unwrap_or_else(|e| {
+        eprintln!("Failed to process arguments: {}", e);
+        process::exit(1);
+    });
+    let puzzle = Puzzle::new(year, args.day);
+
+    if let Err(e) = profiles::activate(args.profile) {
+        eprintln!("Failed to process arguments: {}", e);
+        process::exit(1);
+    }
+
+    let backend: Box<dyn SubmitBackend> = match AocClient::from_env() {
+        Ok(client) => Box::new(client),
+        Err(AocError::SessionNotFound) if aoc_cli::check().is_ok() => Box::new(AocCli),
+        Err(e) => {
+            eprintln!("failed to submit {}: {}", puzzle, e);
+            process::exit(1);
+        }
+    };
+
+    let answer = match args.answer.or_else(|| compute_answer(puzzle, args.part)) {
+        Some(answer) => answer,
+        None => process::exit(1),
+    };
+
+    let mut ledger = Ledger::load(puzzle).unwrap_or_else(|e| {
+        eprintln!("could not read the submission ledger: {}", e);
+        process::exit(1);
+    });
+
+    println!(
+        "Submitting {} for part {} of {}...",
+        answer, args.part, puzzle
+    );
+
+    match submit::submit(
+        backend.as_ref(),
+        &mut ledger,
+        puzzle,
+        args.part,
+        &answer,
+        SystemTime::now(),
+    ) {
+        Ok(Response::Correct) => {
+            println!("{}", Response::Correct);
+            if let Err(e) = Answers::record(puzzle, args.part, &answer) {
+                eprintln!("could not record the answer: {}", e);
+            }
+        }
+        Ok(response) => {
+            println!("{}", response);
+            process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,455 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use crate::profiles;
+use chacha20poly1305::{
+    aead::{Aead, KeyInit, OsRng, Payload},
+    AeadCore, ChaCha20Poly1305, Nonce,
+};
+use std::{
+    env,
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+/// Environment variable holding the key, e.g. a secret of the CI workflow.
+/// Takes precedence over the key file.
+pub const KEY_ENV: &str = "AOC_INPUT_KEY";
+/// File in the crate root holding the key. It is ignored by git.
+pub const KEY_FILE: &str = ".input-key";
+/// Extension appended to the name of an encrypted file, e.g. `src/inputs/01.txt.enc`.
+pub const EXTENSION: &str = "enc";
+/// Folders of `src` whose files are encrypted, with the extension of their files.
+pub const ENCRYPTED_FOLDERS: [(&str, &str); 2] = [("inputs", "txt"), ("answers", "toml")];
+
+const HEADER: &str = "aoc-encrypted-v1";
+const NONCE_LEN: usize = 12;
+
+#[derive(Debug)]
+pub enum CryptError {
+    /// Neither `AOC_INPUT_KEY` nor the key file is set.
+    KeyNotFound,
+    InvalidKey,
+    /// The file was encrypted with another key, or it was damaged.
+    Undecryptable(PathBuf),
+    IoError(io::Error),
+}
+
+impl Display for CryptError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CryptError::KeyNotFound => write!(
+                f,
+                "the file is encrypted and no key was found. Set {} or create \"{}\".",
+                KEY_ENV, KEY_FILE
+            ),
+            CryptError::InvalidKey => write!(f, "the key is not 64 hexadecimal characters."),
+            CryptError::Undecryptable(path) => write!(
+                f,
+                "could not decrypt \"{}\". It was encrypted with another key or is damaged.",
+                path.display()
+            ),
+            CryptError::IoError(e) => write!(f, "could not access encrypted files: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for CryptError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CryptError::IoError(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for CryptError {
+    fn from(e: io::Error) -> Self {
+        CryptError::IoError(e)
+    }
+}
+
+impl From<CryptError> for io::Error {
+    fn from(e: CryptError) -> Self {
+        match e {
+            CryptError::IoError(e) => e,
+            e => io::Error::other(e),
+        }
+    }
+}
+
+/// Whether reading a file failed only because it is encrypted and there is no key.
+pub fn is_missing_key(e: &io::Error) -> bool {
+    e.get_ref()
+        .and_then(|e| e.downcast_ref::<CryptError>())
+        .is_some_and(|e| matches!(e, CryptError::KeyNotFound))
+}
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct Key(chacha20poly1305::Key);
+
+impl std::fmt::Debug for Key {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Key(..)")
+    }
+}
+
+impl Key {
+    pub fn generate() -> Key {
+        Key(ChaCha20Poly1305::generate_key(&mut OsRng))
+    }
+
+    pub fn from_hex(val: &str) -> Result<Key, CryptError> {
+        match from_hex(val.trim()) {
+            Some(bytes) if bytes.len() == 32 => Ok(Key(*chacha20poly1305::Key::from_slice(&bytes))),
+            _ => Err(CryptError::InvalidKey),
+        }
+    }
+
+    pub fn to_hex(&self) -> String {
+        to_hex(&self.0)
+    }
+
+    /// Writes the key to a file only the owner can read.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut options = OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        let mut file = options.open(path)?;
+        profiles::restrict(path, 0o600)?;
+        writeln!(file, "{}", self.to_hex())
+    }
+}
+
+/// Loads the key from `AOC_INPUT_KEY`, falling back to `.input-key`.
+pub fn load_key() -> Result<Option<Key>, CryptError> {
+    match env::var(KEY_ENV) {
+        Ok(val) if !val.trim().is_empty() => return Key::from_hex(&val).map(Some),
+        _ => {}
+    }
+
+    match fs::read_to_string(KEY_FILE) {
+        Ok(val) => Key::from_hex(&val).map(Some),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn from_hex(val: &str) -> Option<Vec<u8>> {
+    if !val.len().is_multiple_of(2) || !val.is_ascii() {
+        return None;
+    }
+    (0..val.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&val[i..i + 2], 16).ok())
+        .collect()
+}
+
+/// Encrypts `plaintext`. `path` is authenticated with it, so that an encrypted file
+/// cannot pass for another one, e.g. the input of another day.
+pub fn encrypt(key: &Key, path: &str, plaintext: &str) -> String {
+    let cipher = ChaCha20Poly1305::new(&key.0);
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let payload = Payload {
+        msg: plaintext.as_bytes(),
+        aad: path.as_bytes(),
+    };
+    let ciphertext = cipher
+        .encrypt(&nonce, payload)
+        .expect("encryption can only fail for messages of many gigabytes");
+
+    format!("{}\n{}{}\n", HEADER, to_hex(&nonce), to_hex(&ciphertext))
+}
+
+/// Decrypts the contents of a file written by [`encrypt`]. Returns `None` if the key or
+/// the path do not match, or if the contents were changed.
+pub fn decrypt(key: &Key, path: &str, contents: &str) -> Option<String> {
+    let data = from_hex(contents.strip_prefix(HEADER)?.trim())?;
+    if data.len() < NONCE_LEN {
+        return None;
+    }
+    let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+
+    let cipher = ChaCha20Poly1305::new(&key.0);
+    let payload = Payload {
+        msg: ciphertext,
+        aad: path.as_bytes(),
+    };
+    let plaintext = cipher.decrypt(Nonce::from_slice(nonce), payload).ok()?;
+    String::from_utf8(plaintext).ok()
+}
+
+/// Path of the encrypted copy of a file, e.g. `src/inputs/01.txt.enc`.
+pub fn encrypted_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".");
+    name.push(EXTENSION);
+    PathBuf::from(name)
+}
+
+/// Path of a file relative to `root`, with `/` as separator. Used as associated data.
+fn relative_name(root: &Path, path: &Path) -> String {
+    let relative = path.strip_prefix(root).unwrap_or(path);
+    relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Reads a file, decrypting its encrypted copy if the file itself does not exist.
+pub fn read_to_string(path: &Path) -> io::Result<String> {
+    read_in(&env::current_dir()?, path, load_key)
+}
+
+pub(crate) fn read_in(
+    root: &Path,
+    path: &Path,
+    key: impl FnOnce() -> Result<Option<Key>, CryptError>,
+) -> io::Result<String> {
+    let e = match fs::read_to_string(path) {
+        Err(e) if e.kind() == io::ErrorKind::NotFound => e,
+        result => return result,
+    };
+
+    let encrypted_path = encrypted_path(path);
+    let contents = match fs::read_to_string(&encrypted_path) {
+        Ok(contents) => contents,
+        // report the missing plaintext file, not its encrypted copy.
+        Err(e2) if e2.kind() == io::ErrorKind::NotFound => return Err(e),
+        Err(e2) => return Err(e2),
+    };
+
+    let key = key()?.ok_or(CryptError::KeyNotFound)?;
+    decrypt(&key, &relative_name(root, path), &contents)
+        .ok_or_else(|| CryptError::Undecryptable(encrypted_path).into())
+}
+
+/// Whether a file exists, or its encrypted copy can be decrypted with the key.
+/// A wrong key or a damaged copy makes the file unreadable.
+pub fn is_readable(path: &Path) -> bool {
+    path.exists() || (encrypted_path(path).exists() && read_to_string(path).is_ok())
+}
+
+/// Files of the encrypted folders in `root`, e.g. `src/inputs/2021/01.txt`, sorted.
+/// With `encrypted`, their encrypted copies are listed instead.
+pub fn files(root: &Path, encrypted: bool) -> io::Result<Vec<PathBuf>> {
+    let mut files = vec![];
+    for (folder, extension) in ENCRYPTED_FOLDERS {
+        let extension = if encrypted {
+            format!("{}.{}", extension, EXTENSION)
+        } else {
+            extension.to_string()
+        };
+        collect(&root.join("src").join(folder), &extension, &mut files)?;
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn collect(dir: &Path, extension: &str, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e),
+    };
+
+    for entry in entries {
+        let path = entry?.path();
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        if path.is_dir() {
+            collect(&path, extension, files)?;
+        } else if name
+            .strip_suffix(extension)
+            .is_some_and(|stem| stem.ends_with('.') && stem.len() > 1)
+        {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Writes the encrypted copy of a file. Returns `false` if the copy is up to date:
+/// encryption is randomized, rewriting unchanged files would change them in git.
+pub fn encrypt_file(key: &Key, root: &Path, path: &Path) -> Result<bool, CryptError> {
+    let plaintext = fs::read_to_string(path)?;
+    let name = relative_name(root, path);
+    let encrypted_path = encrypted_path(path);
+
+    if let Ok(current) = fs::read_to_string(&encrypted_path) {
+        if decrypt(key, &name, &current).as_ref() == Some(&plaintext) {
+            return Ok(false);
+        }
+    }
+
+    fs::write(encrypted_path, encrypt(key, &name, &plaintext))?;
+    Ok(true)
+}
+
+/// Writes the decrypted file of an encrypted copy. Existing files are only replaced with
+/// `overwrite`, they might have been edited since. Returns the path if it was written.
+pub fn decrypt_file(
+    key: &Key,
+    root: &Path,
+    encrypted_path: &Path,
+    overwrite: bool,
+) -> Result<Option<PathBuf>, CryptError> {
+    let path = encrypted_path.with_extension("");
+    if path.exists() && !overwrite {
+        return Ok(None);
+    }
+
+    fs::write(&path, read_encrypted(key, root, encrypted_path)?)?;
+    Ok(Some(path))
+}
+
+/// Decrypts an encrypted copy, e.g. `src/inputs/01.txt.enc`.
+pub fn read_encrypted(key: &Key, root: &Path, encrypted_path: &Path) -> Result<String, CryptError> {
+    let name = relative_name(root, &encrypted_path.with_extension(""));
+    let contents = fs::read_to_string(encrypted_path)?;
+    decrypt(key, &name, &contents)
+        .ok_or_else(|| CryptError::Undecryptable(encrypted_path.to_path_buf()))
+}
+
+/// Re-encrypts an encrypted copy with another key.
+pub fn rotate_file(
+    old: &Key,
+    new: &Key,
+    root: &Path,
+    encrypted_path: &Path,
+) -> Result<(), CryptError> {
+    let name = relative_name(root, &encrypted_path.with_extension(""));
+    let plaintext = read_encrypted(old, root, encrypted_path)?;
+    fs::write(encrypted_path, encrypt(new, &name, &plaintext))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_root(name: &str) -> PathBuf {
+        let root = env::temp_dir().join(format!("aoc-crypt-{}-{}", name, std::process::id()));
+        fs::create_dir_all(root.join("src/inputs/2021")).unwrap();
+        fs::create_dir_all(root.join("src/answers")).unwrap();
+        root
+    }
+
+    #[test]
+    fn test_encrypt() {
+        let key = Key::generate();
+        let encrypted = encrypt(&key, "src/inputs/01.txt", "1000\n2000\n");
+        assert!(encrypted.starts_with("aoc-encrypted-v1\n"));
+        assert!(!encrypted.contains("1000"));
+        assert_ne!(
+            encrypted,
+            encrypt(&key, "src/inputs/01.txt", "1000\n2000\n")
+        );
+
+        assert_eq!(
+            decrypt(&key, "src/inputs/01.txt", &encrypted).as_deref(),
+            Some("1000\n2000\n")
+        );
+        assert_eq!(decrypt(&key, "src/inputs/02.txt", &encrypted), None);
+        assert_eq!(
+            decrypt(&Key::generate(), "src/inputs/01.txt", &encrypted),
+            None
+        );
+        let mut tampered = encrypted.trim_end().to_string();
+        let last = tampered.pop().unwrap();
+        tampered.push(if last == '0' { '1' } else { '0' });
+        assert_eq!(decrypt(&key, "src/inputs/01.txt", &tampered), None);
+        assert_eq!(decrypt(&key, "src/inputs/01.txt", "1000\n"), None);
+    }
+
+    #[test]
+    fn test_key() {
+        let key = Key::generate();
+        assert_eq!(key.to_hex().len(), 64);
+        assert_eq!(Key::from_hex(&format!(" {}\n", key.to_hex())).unwrap(), key);
+        assert!(Key::from_hex("abc").is_err());
+        assert!(Key::from_hex(&"zz".repeat(32)).is_err());
+        assert_eq!(format!("{:?}", key), "Key(..)");
+    }
+
+    #[test]
+    fn test_read() {
+        let root = temp_root("read");
+        let key = Key::generate();
+        let path = root.join("src/inputs/2021/01.txt");
+        fs::write(&path, "1\n2\n").unwrap();
+        assert!(encrypt_file(&key, &root, &path).unwrap());
+        fs::remove_file(&path).unwrap();
+
+        let read = |key: Option<Key>| read_in(&root, &path, || Ok(key));
+        assert_eq!(read(Some(key.clone())).unwrap(), "1\n2\n");
+        assert!(is_missing_key(&read(None).unwrap_err()));
+        let e = read(Some(Key::generate())).unwrap_err();
+        assert!(!is_missing_key(&e));
+        assert!(e.to_string().contains("could not decrypt"));
+
+        let missing = read_in(&root, &root.join("src/inputs/02.txt"), || Ok(None));
+        assert_eq!(missing.unwrap_err().kind(), io::ErrorKind::NotFound);
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_files() {
+        let root = temp_root("files");
+        let (old, new) = (Key::generate(), Key::generate());
+        let input = root.join("src/inputs/2021/01.txt");
+        let answers = root.join("src/answers/01.toml");
+        fs::write(&input, "1\n2\n").unwrap();
+        fs::write(&answers, "part_one = \"3\"\n").unwrap();
+        fs::write(root.join("src/inputs/2021/01.checksum"), "").unwrap();
+        fs::write(root.join("src/inputs/.keep"), "").unwrap();
+
+        assert_eq!(
+            files(&root, false).unwrap(),
+            [answers.clone(), input.clone()]
+        );
+        for path in files(&root, false).unwrap() {
+            assert!(encrypt_file(&old, &root, &path).unwrap());
+        }
+        let encrypted = files(&root, true).unwrap();
+        assert_eq!(
+            encrypted,
+            [encrypted_path(&answers), encrypted_path(&input)]
+        );
+
+        // unchanged files are not encrypted again.
+        let before = fs::read_to_string(encrypted_path(&input)).unwrap();
+        assert!(!encrypt_file(&old, &root, &input).unwrap());
+        assert_eq!(fs::read_to_string(encrypted_path(&input)).unwrap(), before);
+
+        for path in &encrypted {
+            rotate_file(&old, &new, &root, path).unwrap();
+        }
+        assert!(matches!(
+            decrypt_file(&old, &root, &encrypted[1], true),
+            Err(CryptError::Undecryptable(_))
+        ));
+
+        assert_eq!(
+            decrypt_file(&new, &root, &encrypted[1], false).unwrap(),
+            None
+        );
+        fs::write(&input, "edited").unwrap();
+        assert_eq!(
+            decrypt_file(&new, &root, &encrypted[1], true).unwrap(),
+            Some(input.clone())
+        );
+        assert_eq!(fs::read_to_string(&input).unwrap(), "1\n2\n");
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,73 @@
+/*
+ * This file contains template code.
+ * `cargo scaffold` registers new days at the bottom of this file, so there is usually no need to edit it.
+ */
+use crate::{args::RunArgs, puzzle::Puzzle, results::DayResult, runner};
+
+/// Type-erased runner for both parts of a day.
+pub type Runner = fn(&str, &RunArgs) -> DayResult;
+
+/// A day's solution, as exposed by its `src/bin/DD.rs` or `src/bin/YYYY-DD.rs` module.
+pub struct Day {
+    pub puzzle: Puzzle,
+    pub run: Runner,
+}
+
+/// Returns the registered solution for a day, if there is one.
+pub fn get(puzzle: impl Into<Puzzle>) -> Option<&'static Day> {
+    let puzzle = puzzle.into();
+    DAYS.iter().find(|d| d.puzzle == puzzle)
+}
+
+/// Registers days as `N => "bin/NN.rs" as dayNN,` when they export `part_one` and `part_two`,
+/// or as `N => "bin/NN.rs" as dayNN: DayNN,` when `DayNN` implements `Solution`.
+/// Days of a year are registered as `YYYY, N => "bin/YYYY-NN.rs" as yYYYY_dayNN,`.
+macro_rules! register_days {
+    ($($($key:literal),+ => $path:literal as $module:ident $(: $solution:ident)?,)*) => {
+        $(
+            // the day binaries are compiled a second time as library modules.
+            // their `main` functions are only used by `cargo solve`.
+            #[path = $path]
+            #[allow(dead_code)]
+            mod $module;
+        )*
+
+        /// All days with a solution in `src/bin`, in ascending order.
+        pub const DAYS: &[Day] = &[
+            $(Day {
+                puzzle: register_days!(@puzzle $($key),+),
+                run: register_days!(@run ($($key),+), $module $(, $solution)?),
+            },)*
+        ];
+    };
+    (@puzzle $day:literal) => {
+        Puzzle { year: None, day: $day }
+    };
+    (@puzzle $year:literal, $day:literal) => {
+        Puzzle { year: Some($year), day: $day }
+    };
+    (@run ($($key:literal),+), $module:ident) => {
+        |input, args| {
+            let puzzle = register_days!(@puzzle $($key),+);
+            runner::run_parts(puzzle, input, args, $module::part_one, $module::part_two)
+        }
+    };
+    (@run ($($key:literal),+), $module:ident, $solution:ident) => {
+        |input, args| {
+            let puzzle = register_days!(@puzzle $($key),+);
+            runner::run_solution::<$module::$solution>(puzzle, input, args)
+        }
+    };
+}
+
+register_days! {
+    1 => "bin/01.rs" as day01,
+    2 => "bin/02.rs" as day02,
+    3 => "bin/03.rs" as day03,
+    4 => "bin/04.rs" as day04,
+    5 => "bin/05.rs" as day05,
+    6 => "bin/06.rs" as day06,
+    7 => "bin/07.rs" as day07: Day07,
+    8 => "bin/08.rs" as day08,
+    9 => "bin/09.rs" as day09,
+}
@@ -0,0 +1,439 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use crate::{
+    answers::Answers,
+    inputs,
+    puzzle::Puzzle,
+    results::{IntoOutcome, Outcome},
+};
+use std::{fs, io, path::PathBuf};
+
+/// An example input from `src/examples` with its expected answers, if any were recorded.
+///
+/// `DD.txt` is the first example of a day, further examples are stored as `DD-<n>.txt`.
+/// Examples of days of a year live in `src/examples/YYYY`.
+/// Expected answers live next to them in `DD.toml` and `DD-<n>.toml`, using the same
+/// format as `src/answers`.
+#[derive(Debug)]
+pub struct Example {
+    pub name: String,
+    pub input: String,
+    pub answers: Answers,
+}
+
+fn examples_dir(puzzle: Puzzle) -> io::Result<PathBuf> {
+    let path = puzzle.path("examples", "txt")?;
+    Ok(path.parent().map(PathBuf::from).unwrap_or_default())
+}
+
+/// Loads all examples of a day, ordered by their number.
+pub fn load(puzzle: impl Into<Puzzle>) -> io::Result<Vec<Example>> {
+    let puzzle = puzzle.into();
+    let prefix = format!("{:02}", puzzle.day);
+    let dir = examples_dir(puzzle)?;
+
+    let mut numbered = vec![];
+    for entry in fs::read_dir(&dir)? {
+        let file_name = entry?.file_name();
+        let stem = match file_name.to_str().and_then(|f| f.strip_suffix(".txt")) {
+            Some(stem) => stem,
+            None => continue,
+        };
+
+        if stem == prefix {
+            numbered.push((1, stem.to_string()));
+        } else if let Some(n) = stem
+            .strip_prefix(&prefix)
+            .and_then(|rest| rest.strip_prefix('-'))
+            .and_then(|n| n.parse::<u32>().ok())
+        {
+            numbered.push((n, stem.to_string()));
+        }
+    }
+    numbered.sort();
+
+    numbered
+        .into_iter()
+        .map(|(_, stem)| {
+            let input = inputs::normalize(&fs::read_to_string(dir.join(format!("{}.txt", stem)))?);
+            let answers = match fs::read_to_string(dir.join(format!("{}.toml", stem))) {
+                Ok(contents) => Answers::parse(&contents),
+                Err(e) if e.kind() == io::ErrorKind::NotFound => Answers::default(),
+                Err(e) => return Err(e),
+            };
+
+            Ok(Example {
+                name: format!("{}.txt", stem),
+                input,
+                answers,
+            })
+        })
+        .collect()
+}
+
+/// Runs `solver` against every example of a day that has an expected answer for `part`.
+/// Returns a description of each failed example.
+pub fn run<T: IntoOutcome>(
+    puzzle: impl Into<Puzzle>,
+    part: u8,
+    solver: impl Fn(&str) -> T,
+) -> io::Result<Vec<String>> {
+    let puzzle = puzzle.into();
+    let examples: Vec<Example> = load(puzzle)?
+        .into_iter()
+        .filter(|example| example.answers.get(part).is_some())
+        .collect();
+
+    if examples.is_empty() {
+        return Ok(vec![format!(
+            "no example of {} has an expected answer for part {}.",
+            puzzle, part
+        )]);
+    }
+
+    let failures = examples
+        .iter()
+        .filter_map(|example| {
+            let expected = example.answers.get(part)?;
+            let actual = match solver(&example.input).into_outcome() {
+                Outcome::Solved(answer) if answer == expected => return None,
+                Outcome::Solved(answer) => answer,
+                Outcome::Unsolved | Outcome::TimedOut => "not solved".into(),
+                Outcome::Errored(error) => format!("error: {}", error),
+            };
+
+            Some(format!(
+                "example {}: expected {}, got {}",
+                example.name, expected, actual
+            ))
+        })
+        .collect();
+
+    Ok(failures)
+}
+
+/// Test helper that checks `solver` against every example of a day with an expected answer for `part`.
+/// Pass `(year, day)` for days of a year.
+///
+/// ```ignore
+/// #[test]
+/// fn test_part_two() {
+///     advent_of_code::examples::check(9, 2, part_two);
+/// }
+/// ```
+pub fn check<T: IntoOutcome>(puzzle: impl Into<Puzzle>, part: u8, solver: impl Fn(&str) -> T) {
+    let puzzle = puzzle.into();
+    match run(puzzle, part, solver) {
+        Ok(failures) if failures.is_empty() => {}
+        Ok(failures) => panic!("part {} failed:\n{}", part, failures.join("\n")),
+        Err(e) => panic!("could not read examples of {}: {}", puzzle, e),
+    }
+}
+
+/// A fenced code block of a puzzle description.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeBlock {
+    pub text: String,
+    /// The last line of text before the block.
+    pub intro: String,
+}
+
+/// Returns the fenced code blocks of a puzzle description.
+pub fn code_blocks(markdown: &str) -> Vec<CodeBlock> {
+    let mut blocks = vec![];
+    let mut intro = "";
+    let mut lines = markdown.lines();
+
+    while let Some(line) = lines.next() {
+        if line.trim_start().starts_with("```") {
+            let text: Vec<&str> = lines
+                .by_ref()
+                .take_while(|l| !l.trim_start().starts_with("```"))
+                .collect();
+            blocks.push(CodeBlock {
+                text: text.join("\n") + "\n",
+                intro: intro.to_string(),
+            });
+        } else if !line.trim().is_empty() {
+            intro = line;
+        }
+    }
+
+    blocks
+}
+
+/// Index of the block most likely to hold the example input: the first one introduced
+/// as an example, falling back to the longest one.
+pub fn likely_example(blocks: &[CodeBlock]) -> Option<usize> {
+    blocks
+        .iter()
+        .position(|b| b.intro.to_lowercase().contains("example") && !b.text.trim().is_empty())
+        .or_else(|| {
+            (0..blocks.len())
+                .rev()
+                .max_by_key(|&i| blocks[i].text.lines().count())
+        })
+}
+
+/// Returns the emphasized code of a line, written as `` `*6000*` `` or `` *`6000`* ``.
+fn emphasized_code(line: &str) -> Vec<String> {
+    let parts: Vec<&str> = line.split('`').collect();
+
+    (1..parts.len())
+        .step_by(2)
+        .filter(|&i| i + 1 < parts.len())
+        .filter_map(|i| {
+            let code = parts[i];
+            let inner = code.strip_prefix('*').and_then(|c| c.strip_suffix('*'));
+            match inner {
+                Some(inner) if !inner.is_empty() => Some(inner.to_string()),
+                _ if parts[i - 1].ends_with('*') && parts[i + 1].starts_with('*') => {
+                    Some(code.to_string())
+                }
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// Guesses the example answers of both parts of a puzzle description.
+/// The answer of a part is the last emphasized code of its paragraphs. List items usually show
+/// intermediate results or further examples, so they are only used if no paragraph has one.
+pub fn extract_answers(markdown: &str) -> Answers {
+    let mut sections: Vec<Vec<&str>> = vec![vec![]];
+    let mut in_code = false;
+
+    for line in markdown.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code = !in_code;
+        } else if !in_code && line.contains("--- Part Two ---") {
+            sections.push(vec![]);
+        } else if !in_code {
+            sections.last_mut().unwrap().push(line);
+        }
+    }
+
+    let answer = |lines: &Vec<&str>| {
+        let mut paragraph = None;
+        let mut item = None;
+        for line in lines
+            .iter()
+            .take_while(|l| !l.starts_with("Your puzzle answer was"))
+        {
+            let trimmed = line.trim_start();
+            let is_item = trimmed.starts_with("* ") || trimmed.starts_with("- ");
+            if let Some(value) = emphasized_code(line).pop() {
+                match is_item {
+                    true => item = item.or(Some(value)),
+                    false => paragraph = Some(value),
+                }
+            }
+        }
+        paragraph.or(item)
+    };
+
+    Answers {
+        part_one: sections.first().and_then(answer),
+        part_two: sections.get(1).and_then(answer),
+    }
+}
+
+/// Example input and answers extracted from a puzzle description.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Extracted {
+    pub example: Option<String>,
+    pub answers: Answers,
+}
+
+/// Extracts the example of a day from its description in `src/puzzles`.
+/// `pick` selects the n-th code block, counting from 1, instead of the most likely one.
+pub fn extract(puzzle: impl Into<Puzzle>, pick: Option<usize>) -> io::Result<Extracted> {
+    let markdown = fs::read_to_string(puzzle.into().path("puzzles", "md")?)?;
+    let blocks = code_blocks(&markdown);
+
+    let index = match pick {
+        Some(n) if n >= 1 && n <= blocks.len() => Some(n - 1),
+        Some(n) => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "cannot pick code block {}, the puzzle has {}.",
+                    n,
+                    blocks.len()
+                ),
+            ))
+        }
+        None => likely_example(&blocks),
+    };
+
+    Ok(Extracted {
+        example: index.map(|i| blocks[i].text.clone()),
+        answers: extract_answers(&markdown),
+    })
+}
+
+/// Writes an extracted example to `DD.txt` and its answers to `DD.toml` in `src/examples`.
+/// Files that already have contents are kept unless `overwrite` is set.
+/// Returns the paths that were written.
+pub fn save(
+    puzzle: impl Into<Puzzle>,
+    extracted: &Extracted,
+    overwrite: bool,
+) -> io::Result<Vec<PathBuf>> {
+    let puzzle = puzzle.into();
+    let mut written = vec![];
+
+    let answers: String = [(1, "part_one"), (2, "part_two")]
+        .iter()
+        .filter_map(|(part, key)| {
+            let answer = extracted.answers.get(*part)?;
+            Some(format!("{} = \"{}\"\n", key, answer))
+        })
+        .collect();
+
+    let files = [
+        (
+            puzzle.relative_path("examples", "txt"),
+            extracted.example.clone(),
+        ),
+        (
+            puzzle.relative_path("examples", "toml"),
+            Some(answers).filter(|a| !a.is_empty()),
+        ),
+    ];
+
+    for (path, contents) in files {
+        let Some(contents) = contents else { continue };
+        let is_empty = match fs::read_to_string(&path) {
+            Ok(existing) => existing.trim().is_empty(),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => true,
+            Err(e) => return Err(e),
+        };
+
+        if overwrite || is_empty {
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            fs::write(&path, contents)?;
+            written.push(path);
+        }
+    }
+
+    Ok(written)
+}
+
+/// Replaces the `None` placeholders of the scaffolded tests with the extracted answers.
+/// Answers that are not numbers are left for the user to fill in.
+pub fn fill_assertions(module: &str, answers: &Answers) -> String {
+    let mut module = module.to_string();
+
+    for (part, name) in [(1, "part_one"), (2, "part_two")] {
+        let Some(answer) = answers.get(part).filter(|a| a.parse::<u64>().is_ok()) else {
+            continue;
+        };
+        for call in ["(&input)", "(&input).ok()"] {
+            module = module.replace(
+                &format!("assert_eq!({}{}, None);", name, call),
+                &format!("assert_eq!({}{}, Some({}));", name, call, answer),
+            );
+        }
+    }
+
+    module
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load() {
+        let examples = load(9).unwrap();
+        let names: Vec<_> = examples.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["09.txt", "09-2.txt"]);
+        assert_eq!(examples[1].answers.get(2), Some("1"));
+    }
+
+    #[test]
+    fn test_extract() {
+        let extracted = extract(1, None).unwrap();
+        assert_eq!(
+            extracted.example.as_deref(),
+            Some(fs::read_to_string("src/examples/01.txt").unwrap().as_str())
+        );
+        assert_eq!(
+            extracted.answers,
+            Answers {
+                part_one: Some("24000".into()),
+                part_two: Some("45000".into()),
+            }
+        );
+
+        let answers = |day| extract(day, None).unwrap().answers;
+        assert_eq!(answers(5).part_one.as_deref(), Some("CMZ"));
+        assert_eq!(answers(6).part_one.as_deref(), Some("7"));
+        assert_eq!(answers(6).part_two.as_deref(), Some("19"));
+        assert_eq!(answers(7).part_two.as_deref(), Some("24933642"));
+
+        assert_eq!(extract(1, Some(1)).unwrap(), extracted);
+        assert!(extract(1, Some(2)).is_err());
+    }
+
+    #[test]
+    fn test_emphasized_code() {
+        assert_eq!(
+            emphasized_code("a total of `*6000*`, this is *`24000`* and `5000`"),
+            vec!["6000", "24000"]
+        );
+    }
+
+    #[test]
+    fn test_fill_assertions() {
+        let module =
+            "assert_eq!(part_one(&input), None);\nassert_eq!(part_two(&input).ok(), None);\n";
+        let answers = Answers {
+            part_one: Some("CMZ".into()),
+            part_two: Some("45000".into()),
+        };
+        assert_eq!(
+            fill_assertions(module, &answers),
+            "assert_eq!(part_one(&input), None);\nassert_eq!(part_two(&input).ok(), Some(45000));\n"
+        );
+    }
+
+    #[test]
+    fn test_run() {
+        let failures = run(9, 1, |input| Some(input.lines().count())).unwrap();
+        assert_eq!(
+            failures,
+            vec![
+                "example 09.txt: expected 88, got 8",
+                "example 09-2.txt: expected 13, got 8",
+            ]
+        );
+
+        let failures = run(9, 1, |_| None::<u32>).unwrap();
+        assert_eq!(failures[0], "example 09.txt: expected 88, got not solved");
+
+        let failures = run(9, 1, |input| match input.contains("R 17") {
+            true => Some(88),
+            false => Some(13),
+        })
+        .unwrap();
+        assert!(failures.is_empty());
+
+        let failures = run(9, 2, |_| "x".parse::<u32>()).unwrap();
+        assert_eq!(
+            failures[0],
+            "example 09.txt: expected 36, got error: invalid digit found in string"
+        );
+
+        let failures = run(1, 1, |_| Some(0)).unwrap();
+        assert_eq!(
+            failures,
+            vec!["no example of day 01 has an expected answer for part 1."]
+        );
+    }
+}
@@ -0,0 +1,258 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+
+/// Converts the `<article class="day-desc">` elements of a puzzle page to markdown.
+/// Returns `None` if the page contains no puzzle description.
+pub fn puzzle_to_markdown(html: &str) -> Option<String> {
+    let mut articles = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<article") {
+        let after = &rest[start..];
+        let open_end = after.find('>')?;
+        let close = after.find("</article>")?;
+        articles.push(to_markdown(&after[open_end + 1..close]));
+        rest = &after[close + "</article>".len()..];
+    }
+
+    if articles.is_empty() {
+        None
+    } else {
+        Some(articles.join("\n\n") + "\n")
+    }
+}
+
+/// Converts the subset of html used in puzzle descriptions to markdown.
+pub fn to_markdown(html: &str) -> String {
+    let mut out = String::new();
+    let mut links: Vec<String> = vec![];
+    let mut lists = 0;
+    let mut in_pre = false;
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let Some(start) = rest.find('<') else {
+            push_text(&mut out, rest, in_pre);
+            break;
+        };
+        push_text(&mut out, &rest[..start], in_pre);
+
+        let Some(end) = rest[start..].find('>') else {
+            push_text(&mut out, &rest[start..], in_pre);
+            break;
+        };
+        let tag = &rest[start + 1..start + end];
+        rest = &rest[start + end + 1..];
+
+        let closing = tag.starts_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+
+        match (name.as_str(), closing) {
+            ("h2", false) => {
+                start_block(&mut out);
+                out.push_str("## ");
+            }
+            ("h2" | "p", true) => end_block(&mut out),
+            ("p", false) => start_block(&mut out),
+            ("pre", false) => {
+                start_block(&mut out);
+                out.push_str("```\n");
+                in_pre = true;
+            }
+            ("pre", true) => {
+                if !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push_str("```");
+                end_block(&mut out);
+                in_pre = false;
+            }
+            ("code", _) if !in_pre => out.push('`'),
+            // emphasis inside inline code is kept, as in `*6000*`: it marks example answers.
+            ("em", _) if !in_pre => out.push('*'),
+            ("a", false) => {
+                links.push(attribute(tag, "href").unwrap_or_default());
+                out.push('[');
+            }
+            ("a", true) => {
+                let href = links.pop().unwrap_or_default();
+                out.push_str(&format!("]({})", href));
+            }
+            ("ul", false) => {
+                if lists == 0 {
+                    start_block(&mut out);
+                }
+                lists += 1;
+            }
+            ("ul", true) => {
+                lists -= 1;
+                if lists == 0 {
+                    end_block(&mut out);
+                }
+            }
+            ("li", false) => {
+                if !out.is_empty() && !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push_str(&"  ".repeat(lists.max(1) - 1));
+                out.push_str("- ");
+            }
+            ("br", _) => out.push('\n'),
+            _ => {}
+        }
+    }
+
+    out.trim().to_string()
+}
+
+fn start_block(out: &mut String) {
+    if !out.is_empty() && !out.ends_with("\n\n") {
+        end_block(out);
+    }
+}
+
+fn end_block(out: &mut String) {
+    while !out.ends_with("\n\n") {
+        out.push('\n');
+    }
+}
+
+fn push_text(out: &mut String, text: &str, in_pre: bool) {
+    let text = decode_entities(text);
+    if in_pre {
+        out.push_str(&text);
+        return;
+    }
+
+    let mut last_space = out.is_empty() || out.ends_with([' ', '\n']);
+    for c in text.chars() {
+        if c.is_whitespace() {
+            if !last_space {
+                out.push(' ');
+            }
+            last_space = true;
+        } else {
+            out.push(c);
+            last_space = false;
+        }
+    }
+}
+
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let start = tag.find(&format!("{}=\"", name))? + name.len() + 2;
+    let len = tag[start..].find('"')?;
+    Some(decode_entities(&tag[start..start + len]))
+}
+
+/// Decodes the named and numeric character references used on the site.
+pub fn decode_entities(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let decoded = rest.find(';').and_then(|end| {
+            let entity = &rest[1..end];
+            let c = match entity {
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "amp" => Some('&'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                _ => match entity.strip_prefix('#') {
+                    Some(hex) if hex.starts_with(['x', 'X']) => u32::from_str_radix(&hex[1..], 16)
+                        .ok()
+                        .and_then(char::from_u32),
+                    Some(dec) => dec.parse().ok().and_then(char::from_u32),
+                    None => None,
+                },
+            };
+            c.map(|c| (c, end))
+        });
+
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_entities() {
+        assert_eq!(
+            decode_entities("a &lt;b&gt; &amp; &#39;c&#x27;"),
+            "a <b> & 'c'"
+        );
+        assert_eq!(
+            decode_entities("fish & chips &unknown;"),
+            "fish & chips &unknown;"
+        );
+    }
+
+    #[test]
+    fn test_puzzle_to_markdown() {
+        let html = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 1: Calorie Counting ---</h2><p>The jungle
+must be <em>too</em> overgrown, see <a href="/2022/about">the about page</a>.</p>
+<p>For example:</p>
+<pre><code>1000
+<em>2000</em>
+</code></pre>
+<ul><li>The <code>1st</code> Elf, a total of <code><em>6000</em></code></li><li>The second</li></ul>
+<p>Find the Elf carrying the <em>most Calories</em>.</p>
+</article>
+<p>Your puzzle answer was <code>7</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>Sum &amp; more.</p></article>
+</main></body></html>"#;
+
+        let expected = "## --- Day 1: Calorie Counting ---
+
+The jungle must be *too* overgrown, see [the about page](/2022/about).
+
+For example:
+
+```
+1000
+2000
+```
+
+- The `1st` Elf, a total of `*6000*`
+- The second
+
+Find the Elf carrying the *most Calories*.
+
+## --- Part Two ---
+
+Sum & more.
+";
+        assert_eq!(puzzle_to_markdown(html).as_deref(), Some(expected));
+    }
+
+    #[test]
+    fn test_puzzle_to_markdown_without_article() {
+        assert_eq!(puzzle_to_markdown("<html>404 Not Found</html>"), None);
+    }
+}
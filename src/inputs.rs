@@ -0,0 +1,207 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use crate::{crypt, puzzle::Puzzle};
+use std::{
+    fmt::Display,
+    fs::{self, create_dir_all},
+    io,
+    path::PathBuf,
+};
+
+/// Inputs shorter than this are reported, unless they are known to be downloaded as they are.
+/// A few puzzles have inputs this short, e.g. a single number.
+pub const MIN_INPUT_LEN: usize = 16;
+
+const CHECKSUM_PREFIX: &str = "fnv1a64:";
+
+/// Something about an input file that is likely to trip up a solution.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Warning {
+    Empty,
+    TooShort(usize),
+    /// The file has Windows line endings. They are read as `\n`.
+    CrLf,
+    MissingTrailingNewline,
+    /// The file ends with this many empty lines.
+    ExtraTrailingNewlines(usize),
+    /// The first line with a character outside of ASCII, counting from 1.
+    NonAscii(usize),
+    /// The first line with a tab, counting from 1.
+    Tab(usize),
+    /// The file differs from the one written by `cargo download`.
+    Modified,
+}
+
+impl Display for Warning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Warning::Empty => write!(f, "the input is empty. Download it with `cargo download`."),
+            Warning::TooShort(len) => write!(
+                f,
+                "the input is only {} bytes long, was it copied completely?",
+                len
+            ),
+            Warning::CrLf => write!(
+                f,
+                "the input has Windows line endings (CRLF), they are read as \"\\n\"."
+            ),
+            Warning::MissingTrailingNewline => write!(f, "the input does not end with a newline."),
+            Warning::ExtraTrailingNewlines(count) => {
+                write!(f, "the input ends with {} empty line(s).", count)
+            }
+            Warning::NonAscii(line) => {
+                write!(f, "line {} contains a character that is not ASCII.", line)
+            }
+            Warning::Tab(line) => write!(f, "line {} contains a tab.", line),
+            Warning::Modified => write!(
+                f,
+                "the input differs from the downloaded one. Download it again if it was edited by accident."
+            ),
+        }
+    }
+}
+
+/// Drops a byte order mark and converts Windows line endings to `\n`.
+pub fn normalize(input: &str) -> String {
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+    input.replace("\r\n", "\n")
+}
+
+/// Checks an input as it was read from disk. When `checksum` is the one stored by
+/// `cargo download` and it still matches, the input is left alone: it is what the site served.
+pub fn check(raw: &str, checksum: Option<&str>) -> Vec<Warning> {
+    let mut warnings = vec![];
+    match checksum {
+        Some(expected) if expected == self::checksum(raw) => return warnings,
+        Some(_) => warnings.push(Warning::Modified),
+        None => {}
+    }
+
+    let input = normalize(raw);
+    if input.trim().is_empty() {
+        warnings.push(Warning::Empty);
+        return warnings;
+    }
+    if input.len() < MIN_INPUT_LEN {
+        warnings.push(Warning::TooShort(input.len()));
+    }
+    if raw.contains("\r\n") {
+        warnings.push(Warning::CrLf);
+    }
+
+    let empty_lines = input.len() - input.trim_end_matches('\n').len();
+    match empty_lines {
+        0 => warnings.push(Warning::MissingTrailingNewline),
+        1 => {}
+        n => warnings.push(Warning::ExtraTrailingNewlines(n - 1)),
+    }
+
+    let first_line = |pred: fn(char) -> bool| input.lines().position(|l| l.contains(pred));
+    if let Some(line) = first_line(|c| !c.is_ascii()) {
+        warnings.push(Warning::NonAscii(line + 1));
+    }
+    if let Some(line) = first_line(|c| c == '\t') {
+        warnings.push(Warning::Tab(line + 1));
+    }
+
+    warnings
+}
+
+/// FNV-1a hash of the input, enough to notice accidental edits.
+pub fn checksum(input: &str) -> String {
+    let hash = input.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+    format!("{}{:016x}", CHECKSUM_PREFIX, hash)
+}
+
+/// Stores the checksum of a downloaded input next to it, e.g. `src/inputs/01.checksum`.
+pub fn save_checksum(puzzle: Puzzle, input: &str) -> io::Result<PathBuf> {
+    let path = puzzle.relative_path("inputs", "checksum");
+    if let Some(dir) = path.parent() {
+        create_dir_all(dir)?;
+    }
+    fs::write(&path, checksum(input) + "\n")?;
+    Ok(path)
+}
+
+/// Reads the checksum stored by `cargo download`, if any.
+pub fn read_checksum(puzzle: Puzzle) -> Option<String> {
+    let checksum = fs::read_to_string(puzzle.path("inputs", "checksum").ok()?).ok()?;
+    Some(checksum.trim().to_string()).filter(|c| c.starts_with(CHECKSUM_PREFIX))
+}
+
+/// Prints the warnings about the input read from `source`.
+pub fn report(source: &str, warnings: &[Warning]) {
+    for warning in warnings {
+        eprintln!("warning: {}: {}", source, warning);
+    }
+}
+
+/// Reads the input file of a puzzle, reports problems with it and normalizes it.
+/// Encrypted inputs are decrypted, see [`crypt`].
+pub fn read(puzzle: Puzzle) -> io::Result<String> {
+    let path = puzzle.path("inputs", "txt")?;
+    let raw = crypt::read_to_string(&path)?;
+
+    let warnings = check(&raw, read_checksum(puzzle).as_deref());
+    let source = puzzle.relative_path("inputs", "txt");
+    report(&source.display().to_string(), &warnings);
+
+    Ok(normalize(&raw))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("\u{feff}1\r\n2\r\n\r\n3\n"), "1\n2\n\n3\n");
+        assert_eq!(normalize("a\rb\n"), "a\rb\n");
+    }
+
+    #[test]
+    fn test_check() {
+        let input = "1000\n2000\n\n3000\n";
+        assert!(check(input, None).is_empty());
+        assert_eq!(check("", None), [Warning::Empty]);
+        assert_eq!(check("\n\n", None), [Warning::Empty]);
+        assert_eq!(check("42\n", None), [Warning::TooShort(3)]);
+        assert_eq!(
+            check("1000\r\n2000\r\n\r\n30000", None),
+            [Warning::CrLf, Warning::MissingTrailingNewline]
+        );
+        assert_eq!(
+            check("1000\n2000\n\n3000\n\n\n", None),
+            [Warning::ExtraTrailingNewlines(2)]
+        );
+        assert_eq!(
+            check("1000\n2000\n\n3000 é\t\n", None),
+            [Warning::NonAscii(4), Warning::Tab(4)]
+        );
+    }
+
+    #[test]
+    fn test_check_with_checksum() {
+        let short = "42";
+        assert!(check(short, Some(&checksum(short))).is_empty());
+        assert_eq!(
+            check("43", Some(&checksum(short))),
+            [
+                Warning::Modified,
+                Warning::TooShort(2),
+                Warning::MissingTrailingNewline
+            ]
+        );
+    }
+
+    #[test]
+    fn test_checksum() {
+        assert_eq!(checksum(""), "fnv1a64:cbf29ce484222325");
+        assert_eq!(checksum("a"), "fnv1a64:af63dc4c8601ec8c");
+        assert_ne!(checksum("1\n2\n"), checksum("1\r\n2\r\n"));
+    }
+}
@@ -3,173 +3,274 @@
  * There is no need to edit this file unless you want to change template functionality.
  * Prefer `./helpers.rs` if you want to extract code from your solutions.
  */
-use std::env;
+use args::InputSource;
+use puzzle::Puzzle;
 use std::fs;
-
+use std::io;
+use std::process;
+
+// lets the day binaries, which are also compiled as modules of this crate, refer to it by name.
+extern crate self as advent_of_code;
+
+pub mod answers;
+pub mod aoc_client;
+pub mod args;
+pub mod baseline;
+pub mod bench;
+pub mod crypt;
+pub mod days;
+pub mod examples;
 pub mod helpers;
+pub mod html;
+pub mod inputs;
+pub mod markdown;
+pub mod memory;
+pub mod profiles;
+pub mod puzzle;
+pub mod readme;
+pub mod results;
+pub mod runner;
+pub mod solution;
+pub mod submit;
+pub mod templates;
+pub mod unlock;
+
+pub use solution::Solution;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
+/// Runs one part of a day binary, `solve!(1, part_one, input)`,
+/// or all parts of a [`Solution`], `solve!(Day07, input)`.
 #[macro_export]
 macro_rules! solve {
+    (@day) => {
+        // day binaries are named after their zero-padded day and year, e.g. `01` or `2021-01`.
+        option_env!("CARGO_BIN_NAME")
+            .and_then(advent_of_code::puzzle::Puzzle::from_bin_name)
+            .unwrap_or(advent_of_code::puzzle::Puzzle::from(0))
+    };
     ($part:expr, $solver:ident, $input:expr) => {{
-        use advent_of_code::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
-        use std::fmt::Display;
-        use std::time::Instant;
-
-        fn print_result<T: Display>(func: impl FnOnce(&str) -> Option<T>, input: &str) {
-            let timer = Instant::now();
-            let result = func(input);
-            let elapsed = timer.elapsed();
-            match result {
-                Some(result) => {
-                    println!(
-                        "{} {}(elapsed: {:.2?}){}",
-                        result, ANSI_ITALIC, elapsed, ANSI_RESET
-                    );
-                }
-                None => {
-                    println!("not solved.")
-                }
-            }
+        let args = &advent_of_code::args::DayArgs::from_env().run;
+        // once a part timed out, the remaining parts are skipped as by `cargo all`.
+        if !advent_of_code::runner::has_timed_out() {
+            advent_of_code::runner::run_part(advent_of_code::solve!(@day), $part, $solver, $input, args);
         }
 
-        println!("🎄 {}Part {}{} 🎄", ANSI_BOLD, $part, ANSI_RESET);
-        print_result($solver, $input);
+        // parts run in order, so failures can be reported once the last one is done.
+        if $part == advent_of_code::runner::LAST_PART && advent_of_code::runner::has_failed() {
+            std::process::exit(1);
+        }
     }};
-}
-
-pub fn read_file(folder: &str, day: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-
-    let filepath = cwd.join("src").join(folder).join(format!("{:02}.txt", day));
+    ($solution:ty, $input:expr) => {{
+        let args = &advent_of_code::args::DayArgs::from_env().run;
+        advent_of_code::runner::run_solution::<$solution>(advent_of_code::solve!(@day), $input, args);
 
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
-}
-
-fn parse_time(val: &str, postfix: &str) -> f64 {
-    val.split(postfix).next().unwrap().parse().unwrap()
+        if advent_of_code::runner::has_failed() {
+            std::process::exit(1);
+        }
+    }};
 }
 
-pub fn parse_exec_time(output: &str) -> f64 {
-    output.lines().fold(0_f64, |acc, l| {
-        if !l.contains("elapsed:") {
-            acc
-        } else {
-            let timing = l.split("(elapsed: ").last().unwrap();
-            // use `contains` istd. of `ends_with`: string may contain ANSI escape sequences.
-            // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-            if timing.contains("ns)") {
-                acc // range below rounding precision.
-            } else if timing.contains("µs)") {
-                acc + parse_time(timing, "µs") / 1000_f64
-            } else if timing.contains("ms)") {
-                acc + parse_time(timing, "ms")
-            } else if timing.contains("s)") {
-                acc + parse_time(timing, "s") * 1000_f64
-            } else {
-                acc
-            }
+/// Reads the puzzle input for a day binary. Defaults to `src/inputs/DD.txt`,
+/// `--input <path>` reads another file and `--input -` reads from stdin.
+/// Pass `(year, day)` for days of a year, which read `src/inputs/YYYY/DD.txt`.
+/// With a profile, inputs are read from `src/inputs/<profile>`.
+///
+/// Line endings are normalized to `\n`, and problems with the input are reported, see [`inputs::check`].
+pub fn read_input(puzzle: impl Into<Puzzle>) -> String {
+    let input = match &args::DayArgs::from_env().input {
+        InputSource::Default => inputs::read(puzzle.into()),
+        InputSource::Stdin => io::read_to_string(io::stdin()).map(|raw| checked(&raw, "stdin")),
+        InputSource::File(path) => {
+            fs::read_to_string(path).map(|raw| checked(&raw, &path.display().to_string()))
         }
+    };
+
+    input.unwrap_or_else(|e| {
+        eprintln!("could not read input: {}", e);
+        process::exit(1);
     })
 }
 
-/// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-#[cfg(test)]
-macro_rules! assert_approx_eq {
-    ($a:expr, $b:expr) => {{
-        let (a, b) = (&$a, &$b);
-        assert!(
-            (*a - *b).abs() < 1.0e-6,
-            "{} is not approximately equal to {}",
-            *a,
-            *b
-        );
-    }};
+fn checked(raw: &str, source: &str) -> String {
+    inputs::report(source, &inputs::check(raw, None));
+    inputs::normalize(raw)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_parse_exec_time() {
-        assert_approx_eq!(
-            parse_exec_time(&format!(
-                "🎄 Part 1 🎄\n0 (elapsed: 74.13ns){}\n🎄 Part 2 🎄\n0 (elapsed: 50.00ns){}",
-                ANSI_RESET, ANSI_RESET
-            )),
-            0_f64
-        );
-
-        assert_approx_eq!(
-            parse_exec_time("🎄 Part 1 🎄\n0 (elapsed: 755µs)\n🎄 Part 2 🎄\n0 (elapsed: 700µs)"),
-            1.455_f64
-        );
+pub fn read_file(folder: &str, puzzle: impl Into<Puzzle>) -> String {
+    let f = try_read_file(folder, puzzle);
+    f.expect("could not open input file")
+}
 
-        assert_approx_eq!(
-            parse_exec_time("🎄 Part 1 🎄\n0 (elapsed: 70µs)\n🎄 Part 2 🎄\n0 (elapsed: 1.45ms)"),
-            1.52_f64
-        );
+/// Whether a non-empty input file exists for a day. `cargo scaffold` creates empty ones.
+/// Encrypted inputs count if they can be decrypted, which needs the right key.
+pub fn has_input(puzzle: impl Into<Puzzle>) -> bool {
+    let Ok(path) = puzzle.into().path("inputs", "txt") else {
+        return false;
+    };
+    match fs::metadata(&path) {
+        Ok(metadata) => metadata.len() > 0,
+        Err(_) => crypt::is_readable(&path),
+    }
+}
 
-        assert_approx_eq!(
-            parse_exec_time(
-                "🎄 Part 1 🎄\n0 (elapsed: 10.3s)\n🎄 Part 2 🎄\n0 (elapsed: 100.50ms)"
-            ),
-            10400.50_f64
-        );
+/// Reads `src/<folder>/DD.txt` with normalized line endings. Inputs are checked as by `read_input`.
+pub fn try_read_file(folder: &str, puzzle: impl Into<Puzzle>) -> io::Result<String> {
+    let puzzle = puzzle.into();
+    if folder == "inputs" {
+        return inputs::read(puzzle);
     }
+    let filepath = puzzle.path(folder, "txt")?;
+    fs::read_to_string(filepath).map(|text| inputs::normalize(&text))
 }
 
+/// Fallback for `cargo download` and `cargo read` when no session cookie is found.
+/// aoc-cli is optional, [`aoc_client`] talks to the website otherwise.
 pub mod aoc_cli {
+    use crate::{inputs, puzzle::Puzzle};
     use std::{
+        error::Error,
         fmt::Display,
-        fs::create_dir_all,
+        fs::{self, create_dir_all},
+        io::{self, Read, Write},
+        path::Path,
         process::{Command, Output, Stdio},
+        thread,
     };
 
+    #[derive(Debug)]
     pub enum AocCliError {
-        CommandNotFound,
-        CommandNotCallable,
+        CommandNotFound(io::Error),
+        CommandNotCallable(io::Error),
+        /// aoc-cli found no session cookie.
+        SessionNotFound,
+        /// The site rejected the session cookie, usually because it expired.
+        SessionExpired,
+        /// The puzzle is not unlocked yet.
+        PuzzleLocked,
+        /// The site asked to slow down.
+        RateLimited,
+        /// aoc-cli rejected the day or year.
+        InvalidPuzzle,
+        /// aoc-cli failed for another reason. Holds its captured output.
         BadExitStatus(Output),
-        IoError,
+        IoError(io::Error),
     }
 
     impl Display for AocCliError {
         fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             match self {
-                AocCliError::CommandNotFound => write!(f, "aoc-cli is not present in environment."),
-                AocCliError::CommandNotCallable => write!(f, "aoc-cli could not be called."),
-                AocCliError::BadExitStatus(_) => {
-                    write!(f, "aoc-cli exited with a non-zero status.")
+                AocCliError::CommandNotFound(_) => write!(
+                    f,
+                    "aoc-cli is not present in environment. Try running \"cargo install aoc-cli\" to install it."
+                ),
+                AocCliError::CommandNotCallable(_) => write!(f, "aoc-cli could not be called."),
+                AocCliError::SessionNotFound => write!(
+                    f,
+                    "aoc-cli found no session cookie. Paste it into \"~/.adventofcode.session\"."
+                ),
+                AocCliError::SessionExpired => write!(
+                    f,
+                    "the session cookie was rejected. It might have expired, refresh \"~/.adventofcode.session\"."
+                ),
+                AocCliError::PuzzleLocked => write!(
+                    f,
+                    "the puzzle is not unlocked yet. Puzzles unlock at midnight EST (UTC-5)."
+                ),
+                AocCliError::RateLimited => write!(
+                    f,
+                    "the site received too many requests. Wait a few minutes before trying again."
+                ),
+                AocCliError::InvalidPuzzle => write!(
+                    f,
+                    "there is no such puzzle. Days go from 1 to 25, events started in 2015."
+                ),
+                AocCliError::BadExitStatus(output) => {
+                    write!(f, "aoc-cli exited with {}.", output.status)
                 }
-                AocCliError::IoError => write!(f, "could not write output files to file system."),
+                AocCliError::IoError(_) => write!(f, "could not write output files to file system."),
+            }
+        }
+    }
+
+    impl Error for AocCliError {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            match self {
+                AocCliError::CommandNotFound(e)
+                | AocCliError::CommandNotCallable(e)
+                | AocCliError::IoError(e) => Some(e),
+                _ => None,
             }
         }
     }
 
+    /// Recognizes well-known failures in the output of a failed aoc-cli call.
+    pub fn diagnose(output: Output) -> AocCliError {
+        let text = format!(
+            "{}\n{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        )
+        .to_lowercase();
+        let mentions = |patterns: &[&str]| patterns.iter().any(|p| text.contains(p));
+
+        if mentions(&[
+            "session cookie file not found",
+            "failed to read session cookie",
+        ]) {
+            AocCliError::SessionNotFound
+        } else if mentions(&[
+            "invalid session cookie",
+            "please log in",
+            "puzzle inputs differ by user",
+        ]) {
+            AocCliError::SessionExpired
+        } else if mentions(&["too many requests", "429", "rate limit"]) {
+            AocCliError::RateLimited
+        } else if mentions(&["still locked", "before it unlocks", "404 not found"]) {
+            AocCliError::PuzzleLocked
+        } else if mentions(&[
+            "invalid puzzle date",
+            "not a valid advent of code year",
+            "invalid value",
+        ]) {
+            AocCliError::InvalidPuzzle
+        } else {
+            AocCliError::BadExitStatus(output)
+        }
+    }
+
     pub fn check() -> Result<(), AocCliError> {
         Command::new("aoc")
             .arg("-V")
             .output()
-            .map_err(|_| AocCliError::CommandNotFound)?;
+            .map_err(AocCliError::CommandNotFound)?;
         Ok(())
     }
 
-    pub fn read(day: u8, year: Option<u16>) -> Result<Output, AocCliError> {
-        // TODO: output local puzzle if present.
-        let args = build_args("read", &[], day, year);
+    pub fn read(puzzle: Puzzle) -> Result<Output, AocCliError> {
+        let args = build_args("read", &[], puzzle);
         call_aoc_cli(&args)
     }
 
-    pub fn download(day: u8, year: Option<u16>) -> Result<Output, AocCliError> {
-        let input_path = get_input_path(day);
+    /// Submits an answer. Its output holds the response of the site.
+    pub fn submit(puzzle: Puzzle, part: u8, answer: &str) -> Result<Output, AocCliError> {
+        let mut args = build_args("submit", &[], puzzle);
+        args.extend([part.to_string(), answer.to_string()]);
+        call_aoc_cli(&args)
+    }
+
+    pub fn download(puzzle: Puzzle) -> Result<Output, AocCliError> {
+        let input_path = get_input_path(puzzle);
+        let puzzle_path = get_puzzle_path(puzzle);
 
-        let puzzle_path = get_puzzle_path(day);
-        create_dir_all("src/puzzles").map_err(|_| AocCliError::IoError)?;
+        for path in [&input_path, &puzzle_path] {
+            if let Some(dir) = Path::new(path).parent() {
+                create_dir_all(dir).map_err(AocCliError::IoError)?;
+            }
+        }
 
         let args = build_args(
             "download",
@@ -180,34 +281,31 @@ pub mod aoc_cli {
                 "--puzzle-file".into(),
                 puzzle_path.to_string(),
             ],
-            day,
-            year,
+            puzzle,
         );
 
         let output = call_aoc_cli(&args)?;
 
-        if output.status.success() {
-            println!("---");
-            println!("🎄 Successfully wrote input to \"{}\".", &input_path);
-            println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
-            Ok(output)
-        } else {
-            Err(AocCliError::BadExitStatus(output))
-        }
+        let input = fs::read_to_string(&input_path).map_err(AocCliError::IoError)?;
+        inputs::save_checksum(puzzle, &input).map_err(AocCliError::IoError)?;
+
+        println!("---");
+        println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+        println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+        Ok(output)
     }
 
-    fn get_input_path(day: u8) -> String {
-        let day_padded = format!("{:02}", day);
-        format!("src/inputs/{}.txt", day_padded)
+    fn get_input_path(puzzle: Puzzle) -> String {
+        puzzle.relative_path("inputs", "txt").display().to_string()
     }
 
-    fn get_puzzle_path(day: u8) -> String {
-        let day_padded = format!("{:02}", day);
-        format!("src/puzzles/{}.md", day_padded)
+    fn get_puzzle_path(puzzle: Puzzle) -> String {
+        puzzle.relative_path("puzzles", "md").display().to_string()
     }
 
-    fn build_args(command: &str, args: &[String], day: u8, year: Option<u16>) -> Vec<String> {
+    fn build_args(command: &str, args: &[String], puzzle: Puzzle) -> Vec<String> {
         let mut cmd_args = args.to_vec();
+        let Puzzle { year, day } = puzzle;
 
         if let Some(year) = year {
             cmd_args.push("--year".into());
@@ -219,16 +317,121 @@ pub mod aoc_cli {
         cmd_args
     }
 
+    /// Copies `source` to `sink` while it is read, returning everything that was copied.
+    fn tee(mut source: impl Read, mut sink: impl Write) -> Vec<u8> {
+        let mut captured = vec![];
+        let mut buffer = [0; 4096];
+        while let Ok(n) = source.read(&mut buffer) {
+            if n == 0 {
+                break;
+            }
+            // output that cannot be shown is still captured.
+            let _ = sink.write_all(&buffer[..n]);
+            let _ = sink.flush();
+            captured.extend_from_slice(&buffer[..n]);
+        }
+        captured
+    }
+
+    /// Calls aoc-cli, streaming its output while capturing it to diagnose failures.
     fn call_aoc_cli(args: &[String]) -> Result<Output, AocCliError> {
         if cfg!(debug_assertions) {
             println!("Calling >aoc with: {}", args.join(" "));
         }
 
-        Command::new("aoc")
+        let mut child = Command::new("aoc")
             .args(args)
-            .stdout(Stdio::inherit())
-            .stderr(Stdio::inherit())
-            .output()
-            .map_err(|_| AocCliError::CommandNotCallable)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(AocCliError::CommandNotCallable)?;
+
+        let stdout = child
+            .stdout
+            .take()
+            .map(|out| thread::spawn(move || tee(out, io::stdout())));
+        let stderr = child
+            .stderr
+            .take()
+            .map(|err| thread::spawn(move || tee(err, io::stderr())));
+
+        let status = child.wait().map_err(AocCliError::CommandNotCallable)?;
+        let collect = |handle: Option<thread::JoinHandle<Vec<u8>>>| {
+            handle.and_then(|h| h.join().ok()).unwrap_or_default()
+        };
+        let output = Output {
+            status,
+            stdout: collect(stdout),
+            stderr: collect(stderr),
+        };
+
+        if output.status.success() {
+            Ok(output)
+        } else {
+            Err(diagnose(output))
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn failed(stderr: &str) -> Output {
+            // a failed status, as returned by `sh -c "exit 1"`.
+            let status = Command::new("sh").args(["-c", "exit 1"]).status().unwrap();
+            Output {
+                status,
+                stdout: vec![],
+                stderr: stderr.as_bytes().to_vec(),
+            }
+        }
+
+        #[test]
+        fn test_diagnose() {
+            let diagnose = |stderr| diagnose(failed(stderr));
+
+            assert!(matches!(
+                diagnose("error: Session cookie file not found in home or config directory"),
+                AocCliError::SessionNotFound
+            ));
+            assert!(matches!(
+                diagnose("error: Invalid session cookie"),
+                AocCliError::SessionExpired
+            ));
+            assert!(matches!(
+                diagnose("error: Puzzle 25 of 2099 is still locked"),
+                AocCliError::PuzzleLocked
+            ));
+            assert!(matches!(
+                diagnose(
+                    "error: HTTP request error: HTTP status client error (429 Too Many Requests)"
+                ),
+                AocCliError::RateLimited
+            ));
+            assert!(matches!(
+                diagnose("error: invalid value '26' for '--day <DAY>'"),
+                AocCliError::InvalidPuzzle
+            ));
+            assert!(matches!(
+                diagnose("error: something else"),
+                AocCliError::BadExitStatus(_)
+            ));
+        }
+
+        #[test]
+        fn test_tee() {
+            let mut sink = vec![];
+            let captured = tee("line one\nline two\n".as_bytes(), &mut sink);
+            assert_eq!(captured, b"line one\nline two\n");
+            assert_eq!(sink, captured);
+        }
+
+        #[test]
+        fn test_build_args() {
+            assert_eq!(
+                build_args("read", &["--overwrite".into()], Puzzle::from((2021, 7))),
+                ["--overwrite", "--year", "2021", "--day", "7", "read"]
+            );
+        }
     }
 }
@@ -2,47 +2,204 @@
  * This file contains template code.
  * There is no need to edit this file unless you want to change template functionality.
  */
-use advent_of_code::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
-use std::process::Command;
+use advent_of_code::{
+    args::{self, RunArgs},
+    baseline::Baseline,
+    days,
+    memory::{self, AllocStats},
+    puzzle::{self, Puzzle},
+    readme,
+    results::{DayResult, Outcome, ParseResult, PartResult},
+    runner, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
+use std::{process, time::Duration};
+
+struct Args {
+    run: RunArgs,
+    year: Option<u16>,
+    days: Vec<u8>,
+    with_inputs: bool,
+    readme: bool,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    let run = RunArgs::parse(&mut args)?;
+    let year =
+        puzzle::year_or_default(args.opt_value_from_fn(["-y", "--year"], puzzle::parse_year)?)
+            .map_err(|cause| pico_args::Error::ArgumentParsingFailed { cause })?;
+    let days = args.opt_value_from_fn(["-d", "--days"], args::parse_days)?;
+    let with_inputs = args.contains("--with-inputs");
+    let readme = args.contains("--readme");
+    args::finish(args)?;
+
+    // the table is rewritten as a whole, a partial run would drop the other rows.
+    if readme && (days.is_some() || run.part.is_some()) {
+        return Err(pico_args::Error::ArgumentParsingFailed {
+            cause: "--readme runs every day and part, it cannot be combined with --days or --part"
+                .into(),
+        });
+    }
+
+    Ok(Args {
+        run,
+        year,
+        days: days.unwrap_or_else(|| (1..=25).collect()),
+        with_inputs,
+        readme,
+    })
+}
 
 fn main() {
-    let total: f64 = (1..=25)
-        .map(|day| {
-            let day = format!("{:02}", day);
+    let Args {
+        run: args,
+        year,
+        days: selected_days,
+        with_inputs,
+        readme,
+    } = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {}", e);
+            process::exit(1);
+        }
+    };
+    let args = &args;
+    args.activate_profile();
 
-            let mut args = vec!["run", "--bin", &day];
-            if cfg!(not(debug_assertions)) {
-                args.push("--release");
+    // loaded before running so that `--save-baseline` cannot overwrite the timings being compared to.
+    let baseline = if args.compare {
+        match Baseline::load(year, &args.baseline) {
+            Ok(baseline) => Some(baseline),
+            Err(e) => {
+                eprintln!("could not read baseline \"{}\": {}", args.baseline, e);
+                process::exit(1);
             }
+        }
+    } else {
+        None
+    };
 
-            let cmd = Command::new("cargo").args(&args).output().unwrap();
-
+    let days: Vec<DayResult> = selected_days
+        .into_iter()
+        .map(|day| Puzzle::new(year, day))
+        .filter(|puzzle| !with_inputs || advent_of_code::has_input(*puzzle))
+        .map(|puzzle| {
             println!("----------");
-            println!("{}| Day {} |{}", ANSI_BOLD, day, ANSI_RESET);
+            println!("{}| Day {:02} |{}", ANSI_BOLD, puzzle.day, ANSI_RESET);
             println!("----------");
 
-            let output = String::from_utf8(cmd.stdout).unwrap();
-            let is_empty = output.is_empty();
-
-            println!(
-                "{}",
-                if is_empty {
-                    "Not solved."
-                } else {
-                    output.trim()
+            let solution = match days::get(puzzle) {
+                Some(solution) => solution,
+                None => {
+                    println!("Skipped, not scaffolded.");
+                    return DayResult::default();
                 }
-            );
+            };
 
-            if is_empty {
-                0_f64
-            } else {
-                advent_of_code::parse_exec_time(&output)
+            let result = runner::run_day(solution, args);
+
+            if let Some(reason) = &result.skipped {
+                println!("Skipped, {}", reason);
+            } else if result.parts.is_empty() {
+                println!("Not solved.");
             }
+
+            result
         })
-        .sum();
+        .collect();
+
+    let parses: Vec<ParseResult> = days.iter().filter_map(|d| d.parse).collect();
+    let results: Vec<PartResult> = days.into_iter().flat_map(|d| d.parts).collect();
+
+    // parse steps of `Solution` days count towards the total like the parsing inside parts does.
+    let total: Duration = parses.iter().map(|p| p.elapsed).sum::<Duration>()
+        + results
+            .iter()
+            .filter(|r| !r.is_timed_out())
+            .map(|r| r.elapsed)
+            .sum::<Duration>();
+
+    let memory = match memory::is_counting() {
+        true => format!(
+            ", {}",
+            AllocStats::total(
+                parses
+                    .iter()
+                    .filter_map(|p| p.memory)
+                    .chain(results.iter().filter_map(|r| r.memory))
+            )
+        ),
+        false => String::new(),
+    };
 
     println!(
-        "{}Total:{} {}{:.2}ms{}",
-        ANSI_BOLD, ANSI_RESET, ANSI_ITALIC, total, ANSI_RESET
+        "{}Total:{} {}{:.2}ms{}{}",
+        ANSI_BOLD,
+        ANSI_RESET,
+        ANSI_ITALIC,
+        total.as_secs_f64() * 1000_f64,
+        memory,
+        ANSI_RESET
     );
+
+    let count = |f: fn(&PartResult) -> bool| results.iter().filter(|r| f(r)).count();
+    let errored = count(PartResult::is_errored);
+    let timed_out = count(PartResult::is_timed_out);
+    println!(
+        "{} solved, {} unsolved, {} errored, {} timed out.",
+        count(PartResult::is_solved),
+        count(|r| r.outcome == Outcome::Unsolved),
+        errored,
+        timed_out
+    );
+
+    let stray = runner::stray_parts();
+    if stray > 0 {
+        eprintln!(
+            "{} timed out part(s) kept running in the background, timings and allocations measured meanwhile are unreliable.",
+            stray
+        );
+    }
+
+    let mut failed = false;
+
+    if readme {
+        match readme::update(year, &parses, &results) {
+            Ok(_) => println!("Updated benchmarks in \"{}\".", readme::README_PATH),
+            Err(e) => {
+                eprintln!("{}", e);
+                failed = true;
+            }
+        }
+    }
+
+    if errored > 0 {
+        eprintln!("Some parts panicked or returned an error.");
+        failed = true;
+    }
+
+    if timed_out > 0 {
+        eprintln!("Some parts timed out.");
+        failed = true;
+    }
+
+    if runner::has_regressions(&results) {
+        eprintln!("Some parts no longer produce their recorded answer.");
+        failed = true;
+    }
+
+    if let Some(baseline) = baseline {
+        if runner::has_slowdowns(&results, &baseline, args.threshold) {
+            eprintln!(
+                "Some parts are more than {}% slower than baseline \"{}\".",
+                args.threshold, args.baseline
+            );
+            failed = true;
+        }
+    }
+
+    if failed {
+        process::exit(1);
+    }
 }
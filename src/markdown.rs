@@ -0,0 +1,327 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::{
+    env,
+    io::{self, IsTerminal, Write},
+    process::{Command, Stdio},
+};
+
+const DEFAULT_WIDTH: usize = 80;
+const DEFAULT_HEIGHT: usize = 24;
+const RESET: &str = "\x1b[0m";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Style {
+    Plain,
+    Heading,
+    Emphasis,
+    Code,
+    EmphasizedCode,
+    Link,
+}
+
+impl Style {
+    fn escape(&self) -> &'static str {
+        match self {
+            Style::Plain => "",
+            Style::Heading => "\x1b[1;32m",
+            Style::Emphasis => "\x1b[1;97m",
+            Style::Code => "\x1b[36m",
+            Style::EmphasizedCode => "\x1b[1;96m",
+            Style::Link => "\x1b[4m",
+        }
+    }
+}
+
+type Word = Vec<(char, Style)>;
+
+/// Renders the markdown of a puzzle description for the terminal, as written by `cargo download`
+/// or by aoc-cli. Text is wrapped to `width` columns, code blocks are kept as they are.
+/// Without `color`, the markup is dropped and no escape codes are written.
+pub fn render(markdown: &str, width: usize, color: bool) -> String {
+    let mut out = String::new();
+    let mut paragraph: Vec<&str> = vec![];
+    let mut lines = markdown.lines();
+
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim_start();
+
+        if trimmed.is_empty() || trimmed.starts_with("```") || trimmed.starts_with("## ") {
+            flush_paragraph(&mut out, &mut paragraph, Style::Plain, width, color);
+        }
+
+        if is_underline(trimmed) && !paragraph.is_empty() {
+            // a setext heading, the paragraph above is its text.
+            flush_paragraph(&mut out, &mut paragraph, Style::Heading, width, color);
+            out.push('\n');
+        } else if is_underline(trimmed) {
+            // a horizontal rule.
+        } else if trimmed.starts_with("```") {
+            for code in lines
+                .by_ref()
+                .take_while(|l| !l.trim_start().starts_with("```"))
+            {
+                if !code.is_empty() {
+                    out.push_str("    ");
+                    out.push_str(&styled(code, Style::Code, color));
+                }
+                out.push('\n');
+            }
+            out.push('\n');
+        } else if let Some(heading) = trimmed.strip_prefix("## ") {
+            let words = split_words(&parse_inline(heading, Style::Heading));
+            out.push_str(&wrap(&words, width, "", "", color));
+            out.push('\n');
+        } else if let Some(item) = trimmed
+            .strip_prefix("- ")
+            .or_else(|| trimmed.strip_prefix("* "))
+        {
+            flush_paragraph(&mut out, &mut paragraph, Style::Plain, width, color);
+            let indent = " ".repeat(line.len() - trimmed.len());
+            let words = split_words(&parse_inline(item, Style::Plain));
+            let first = format!("{}• ", indent);
+            let rest = format!("{}  ", indent);
+            out.push_str(&wrap(&words, width, &first, &rest, color));
+        } else if !trimmed.is_empty() {
+            paragraph.push(trimmed);
+        } else if !out.is_empty() && !out.ends_with("\n\n") {
+            out.push('\n');
+        }
+    }
+    flush_paragraph(&mut out, &mut paragraph, Style::Plain, width, color);
+
+    out.trim_end().to_string() + "\n"
+}
+
+fn is_underline(line: &str) -> bool {
+    line.len() >= 3 && (line.chars().all(|c| c == '-') || line.chars().all(|c| c == '='))
+}
+
+fn flush_paragraph(
+    out: &mut String,
+    paragraph: &mut Vec<&str>,
+    style: Style,
+    width: usize,
+    color: bool,
+) {
+    if paragraph.is_empty() {
+        return;
+    }
+    let words = split_words(&parse_inline(&paragraph.join(" "), style));
+    out.push_str(&wrap(&words, width, "", "", color));
+    paragraph.clear();
+}
+
+fn styled(text: &str, style: Style, color: bool) -> String {
+    if color && style != Style::Plain {
+        format!("{}{}{}", style.escape(), text, RESET)
+    } else {
+        text.to_string()
+    }
+}
+
+/// Resolves `*emphasis*`, `` `code` ``, `[links](href)` and `\*` escapes to styled characters.
+/// Emphasis inside code, as in `` `*6000*` ``, is kept.
+fn parse_inline(text: &str, base: Style) -> Vec<(char, Style)> {
+    let mut chars = vec![];
+    let mut style = base;
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        rest = &rest[c.len_utf8()..];
+        let in_code = matches!(style, Style::Code | Style::EmphasizedCode);
+        match c {
+            '\\' if !in_code && !rest.is_empty() => {
+                let escaped = rest.chars().next().unwrap_or(c);
+                chars.push((escaped, style));
+                rest = &rest[escaped.len_utf8()..];
+            }
+            '`' if in_code => style = base,
+            '`' => style = Style::Code,
+            '*' if style == Style::Code => style = Style::EmphasizedCode,
+            '*' if style == Style::EmphasizedCode => style = Style::Code,
+            '*' if style == Style::Emphasis => style = base,
+            '*' => style = Style::Emphasis,
+            '[' if !in_code => {
+                let link = rest
+                    .find("](")
+                    .and_then(|end| Some((end, end + rest[end..].find(')')?)));
+                match link {
+                    Some((end, close)) => {
+                        chars.extend(parse_inline(&rest[..end], Style::Link));
+                        rest = &rest[close + 1..];
+                    }
+                    None => chars.push((c, style)),
+                }
+            }
+            c => chars.push((c, style)),
+        }
+    }
+
+    chars
+}
+
+fn split_words(chars: &[(char, Style)]) -> Vec<Word> {
+    chars
+        .split(|(c, _)| c.is_whitespace())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_vec())
+        .collect()
+}
+
+/// Wraps words to `width` columns, prefixing the first line with `first` and the others with `rest`.
+fn wrap(words: &[Word], width: usize, first: &str, rest: &str, color: bool) -> String {
+    let mut out = String::new();
+    let mut column = 0;
+
+    for word in words {
+        if column == 0 {
+            let prefix = if out.is_empty() { first } else { rest };
+            out.push_str(prefix);
+            column = prefix.chars().count();
+        } else if column + 1 + word.len() > width {
+            out.push('\n');
+            out.push_str(rest);
+            column = rest.chars().count();
+        } else {
+            out.push(' ');
+            column += 1;
+        }
+
+        out.push_str(&render_word(word, color));
+        column += word.len();
+    }
+
+    out.push('\n');
+    out
+}
+
+fn render_word(word: &Word, color: bool) -> String {
+    let mut out = String::new();
+    let mut start = 0;
+
+    while start < word.len() {
+        let style = word[start].1;
+        let len = word[start..]
+            .iter()
+            .take_while(|(_, s)| *s == style)
+            .count();
+        let text: String = word[start..start + len].iter().map(|(c, _)| c).collect();
+        out.push_str(&styled(&text, style, color));
+        start += len;
+    }
+
+    out
+}
+
+fn terminal_size(var: &str, default: usize) -> usize {
+    env::var(var)
+        .ok()
+        .and_then(|val| val.trim().parse().ok())
+        .filter(|size| *size > 0)
+        .unwrap_or(default)
+}
+
+/// Width to wrap to, read from `COLUMNS`.
+pub fn terminal_width() -> usize {
+    terminal_size("COLUMNS", DEFAULT_WIDTH)
+}
+
+/// Whether to style output. Disabled when stdout is not a terminal or `NO_COLOR` is set.
+pub fn use_color() -> bool {
+    io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none()
+}
+
+/// Prints `text`, piping it through `$PAGER` (or `less -R`) if it does not fit on the screen.
+pub fn page(text: &str) {
+    let height = terminal_size("LINES", DEFAULT_HEIGHT);
+    if !io::stdout().is_terminal() || text.lines().count() < height {
+        print!("{}", text);
+        return;
+    }
+
+    let pager = env::var("PAGER").unwrap_or_else(|_| "less -R".into());
+    let mut words = pager.split_whitespace();
+    let child = words.next().and_then(|program| {
+        Command::new(program)
+            .args(words)
+            .stdin(Stdio::piped())
+            .spawn()
+            .ok()
+    });
+
+    match child {
+        Some(mut child) => {
+            if let Some(mut stdin) = child.stdin.take() {
+                // the pager may be closed before reading everything.
+                let _ = stdin.write_all(text.as_bytes());
+            }
+            let _ = child.wait();
+        }
+        None => print!("{}", text),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUZZLE: &str = "## --- Day 1: Calorie Counting ---
+
+The jungle must be *too* overgrown, see [the about page](/2022/about).
+
+```
+1000
+2000
+```
+
+- The `1st` Elf carries a lot
+- The second
+
+Find the *most Calories*.
+";
+
+    #[test]
+    fn test_render_plain() {
+        assert_eq!(
+            render(PUZZLE, 24, false),
+            "--- Day 1: Calorie
+Counting ---
+
+The jungle must be too
+overgrown, see the about
+page.
+
+    1000
+    2000
+
+• The 1st Elf carries a
+  lot
+• The second
+
+Find the most Calories.
+"
+        );
+    }
+
+    #[test]
+    fn test_render_color() {
+        let rendered = render("Hello *there*, `x`!\n", 80, true);
+        assert_eq!(
+            rendered,
+            "Hello \x1b[1;97mthere\x1b[0m, \x1b[36mx\x1b[0m!\n"
+        );
+    }
+
+    #[test]
+    fn test_render_aoc_cli() {
+        let puzzle = "\\--- Day 1: Calorie Counting ---\n----------\n\n* A total of `*6000*` Calories.\n\nOne gold star: \\*\n";
+        assert_eq!(
+            render(puzzle, 80, false),
+            "--- Day 1: Calorie Counting ---\n\n• A total of 6000 Calories.\n\nOne gold star: *\n"
+        );
+        assert!(render(puzzle, 80, true).contains("\x1b[1;96m6000\x1b[0m"));
+    }
+}
@@ -0,0 +1,198 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    fmt::Display,
+};
+
+/// Global allocator that counts the allocations of each thread.
+/// Installed with the `count-allocations` feature, e.g. `cargo solve 01 --features count-allocations`.
+pub struct CountingAllocator;
+
+#[cfg(feature = "count-allocations")]
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+// `const` thread locals need no allocation or destructor, so the allocator may use them.
+thread_local! {
+    static ALLOCATIONS: Cell<u64> = const { Cell::new(0) };
+    static ALLOCATED: Cell<u64> = const { Cell::new(0) };
+    static LIVE: Cell<i64> = const { Cell::new(0) };
+    static PEAK: Cell<i64> = const { Cell::new(0) };
+}
+
+fn count_allocation(size: usize) {
+    ALLOCATIONS.set(ALLOCATIONS.get() + 1);
+    ALLOCATED.set(ALLOCATED.get() + size as u64);
+    resize_live(size as i64);
+}
+
+fn resize_live(change: i64) {
+    let live = LIVE.get() + change;
+    LIVE.set(live);
+    PEAK.set(PEAK.get().max(live));
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            count_allocation(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            count_allocation(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        resize_live(-(layout.size() as i64));
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            count_allocation(new_size);
+            resize_live(-(layout.size() as i64));
+        }
+        new_ptr
+    }
+}
+
+/// Memory used by a single run of a part.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    /// Number of allocations, counting every reallocation as a new one.
+    pub allocations: u64,
+    /// Total bytes requested by these allocations.
+    pub allocated: u64,
+    /// Highest number of bytes that were live at once.
+    pub peak: u64,
+}
+
+impl AllocStats {
+    /// Adds up allocations and bytes, keeping the highest peak.
+    pub fn total(stats: impl IntoIterator<Item = AllocStats>) -> AllocStats {
+        stats
+            .into_iter()
+            .fold(AllocStats::default(), |total, stats| AllocStats {
+                allocations: total.allocations + stats.allocations,
+                allocated: total.allocated + stats.allocated,
+                peak: total.peak.max(stats.peak),
+            })
+    }
+}
+
+impl Display for AllocStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} allocations, {} allocated, {} peak",
+            self.allocations,
+            format_bytes(self.allocated),
+            format_bytes(self.peak)
+        )
+    }
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+
+    let mut value = bytes as f64 / 1024_f64;
+    let mut unit = 0;
+    while value >= 1024_f64 && unit < UNITS.len() - 1 {
+        value /= 1024_f64;
+        unit += 1;
+    }
+    format!("{:.2} {}", value, UNITS[unit])
+}
+
+/// Whether the counting allocator is installed.
+pub fn is_counting() -> bool {
+    cfg!(feature = "count-allocations")
+}
+
+/// Runs `func` and returns the memory it allocated on the current thread.
+/// Without the `count-allocations` feature, no stats are returned.
+pub fn track<T>(func: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    if !is_counting() {
+        return (func(), None);
+    }
+
+    let (allocations, allocated, live) = (ALLOCATIONS.get(), ALLOCATED.get(), LIVE.get());
+    PEAK.set(live);
+
+    let output = func();
+
+    let stats = AllocStats {
+        allocations: ALLOCATIONS.get() - allocations,
+        allocated: ALLOCATED.get() - allocated,
+        peak: (PEAK.get() - live).max(0) as u64,
+    };
+    (output, Some(stats))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.50 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.00 MiB");
+    }
+
+    #[test]
+    fn test_total() {
+        let total = AllocStats::total([
+            AllocStats {
+                allocations: 2,
+                allocated: 100,
+                peak: 80,
+            },
+            AllocStats {
+                allocations: 3,
+                allocated: 50,
+                peak: 90,
+            },
+        ]);
+        assert_eq!(
+            total,
+            AllocStats {
+                allocations: 5,
+                allocated: 150,
+                peak: 90,
+            }
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "count-allocations")]
+    fn test_track() {
+        let (_, stats) = track(|| {
+            let mut v: Vec<u64> = Vec::with_capacity(8);
+            v.extend(0..8);
+            drop(v);
+            vec![0_u8; 100]
+        });
+
+        let stats = stats.unwrap();
+        assert_eq!(stats.allocations, 2);
+        assert_eq!(stats.allocated, 164);
+        assert_eq!(stats.peak, 100);
+    }
+}
@@ -0,0 +1,319 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::{
+    env,
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+/// Environment variable holding the profile used when `--profile` is not passed.
+pub const PROFILE_ENV: &str = "AOC_PROFILE";
+/// Folders of `src` that are kept apart per profile, because their files depend on the account.
+pub const PROFILE_FOLDERS: [&str; 4] = ["inputs", "answers", "submissions", "benchmarks"];
+
+const PROFILES_DIR: &str = ".adventofcode.profiles";
+
+static ACTIVE: OnceLock<Option<String>> = OnceLock::new();
+
+#[derive(Debug)]
+pub enum ProfileError {
+    InvalidName(String),
+    InvalidSession,
+    NotFound(String),
+    /// Another profile is active already. Holds the active profile and the requested one.
+    AlreadyActive(Option<String>, Option<String>),
+    IoError(io::Error),
+}
+
+impl Display for ProfileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProfileError::InvalidName(name) => write!(
+                f,
+                "\"{}\" is not a valid profile name. Use letters, digits, \"-\" and \"_\", and at least one letter.",
+                name
+            ),
+            ProfileError::InvalidSession => write!(
+                f,
+                "this does not look like a session cookie, which is 128 hexadecimal characters."
+            ),
+            ProfileError::NotFound(name) => write!(
+                f,
+                "no session cookie stored for profile \"{}\". Run `cargo profile login {}`.",
+                name, name
+            ),
+            ProfileError::AlreadyActive(active, requested) => {
+                let name = |profile: &Option<String>| match profile {
+                    Some(name) => format!("profile \"{}\"", name),
+                    None => "no profile".into(),
+                };
+                write!(
+                    f,
+                    "cannot switch to {}, {} is active already.",
+                    name(requested),
+                    name(active)
+                )
+            }
+            ProfileError::IoError(e) => write!(f, "could not access profiles: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ProfileError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ProfileError::IoError(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ProfileError {
+    fn from(e: io::Error) -> Self {
+        ProfileError::IoError(e)
+    }
+}
+
+/// Checks that `name` can be used as a directory name. Names made of digits only are
+/// rejected, they would be mistaken for the year directories, e.g. `src/inputs/2021`.
+pub fn parse_name(val: &str) -> Result<String, ProfileError> {
+    let name = val.trim();
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        && name.chars().any(|c| c.is_ascii_alphabetic());
+
+    if valid {
+        Ok(name.to_string())
+    } else {
+        Err(ProfileError::InvalidName(name.to_string()))
+    }
+}
+
+/// Checks the format of a session cookie, as copied from the browser.
+/// A leading `session=` is dropped. Older cookies have 96 characters.
+pub fn parse_session(val: &str) -> Result<String, ProfileError> {
+    let session = val.trim();
+    let session = session.strip_prefix("session=").unwrap_or(session);
+
+    if matches!(session.len(), 96 | 128) && session.chars().all(|c| c.is_ascii_hexdigit()) {
+        Ok(session.to_string())
+    } else {
+        Err(ProfileError::InvalidSession)
+    }
+}
+
+/// Selects the profile of this process. Call it before any paths are resolved:
+/// the profile is read once, falling back to `AOC_PROFILE` when `profile` is `None`.
+/// Selecting the active profile again is fine, switching to another one is an error.
+pub fn activate(profile: Option<String>) -> Result<(), ProfileError> {
+    let profile = match profile {
+        Some(profile) => Some(parse_name(&profile)?),
+        None => from_env()?,
+    };
+    set(&ACTIVE, profile)
+}
+
+fn set(active: &OnceLock<Option<String>>, profile: Option<String>) -> Result<(), ProfileError> {
+    match active.set(profile) {
+        Ok(()) => Ok(()),
+        Err(profile) if active.get() == Some(&profile) => Ok(()),
+        Err(profile) => Err(ProfileError::AlreadyActive(
+            active.get().cloned().flatten(),
+            profile,
+        )),
+    }
+}
+
+/// The profile of this process, if any.
+pub fn active() -> Option<&'static str> {
+    ACTIVE.get_or_init(|| from_env().ok().flatten()).as_deref()
+}
+
+fn from_env() -> Result<Option<String>, ProfileError> {
+    match env::var(PROFILE_ENV) {
+        Ok(val) if !val.trim().is_empty() => parse_name(&val).map(Some),
+        _ => Ok(None),
+    }
+}
+
+/// Session cookies of the profiles, one file per profile in `~/.adventofcode.profiles`.
+/// Only the owner can read them.
+pub struct Profiles {
+    dir: PathBuf,
+}
+
+impl Profiles {
+    pub fn new(dir: impl Into<PathBuf>) -> Profiles {
+        Profiles { dir: dir.into() }
+    }
+
+    pub fn from_home() -> Result<Profiles, ProfileError> {
+        let home = env::var_os("HOME")
+            .or_else(|| env::var_os("USERPROFILE"))
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no home directory found"))?;
+        Ok(Profiles::new(Path::new(&home).join(PROFILES_DIR)))
+    }
+
+    fn path(&self, name: &str) -> PathBuf {
+        self.dir.join(format!("{}.session", name))
+    }
+
+    /// Stores the session cookie of a profile, replacing the previous one.
+    pub fn save(&self, name: &str, session: &str) -> Result<PathBuf, ProfileError> {
+        let name = parse_name(name)?;
+        let session = parse_session(session)?;
+
+        fs::create_dir_all(&self.dir)?;
+        restrict(&self.dir, 0o700)?;
+
+        let path = self.path(&name);
+        let mut options = OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        let mut file = options.open(&path)?;
+        // the mode only applies to new files.
+        restrict(&path, 0o600)?;
+        writeln!(file, "{}", session)?;
+
+        Ok(path)
+    }
+
+    /// Reads the session cookie of a profile.
+    pub fn session(&self, name: &str) -> Result<String, ProfileError> {
+        match fs::read_to_string(self.path(&parse_name(name)?)) {
+            Ok(session) => parse_session(&session),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                Err(ProfileError::NotFound(name.to_string()))
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn remove(&self, name: &str) -> Result<(), ProfileError> {
+        match fs::remove_file(self.path(&parse_name(name)?)) {
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                Err(ProfileError::NotFound(name.to_string()))
+            }
+            result => Ok(result?),
+        }
+    }
+
+    /// Names of the stored profiles, sorted.
+    pub fn list(&self) -> Result<Vec<String>, ProfileError> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut names = vec![];
+        for entry in entries {
+            let file_name = entry?.file_name();
+            if let Some(name) = file_name.to_str().and_then(|f| f.strip_suffix(".session")) {
+                names.push(name.to_string());
+            }
+        }
+        names.sort();
+        Ok(names)
+    }
+}
+
+#[cfg(unix)]
+pub(crate) fn restrict(path: &Path, mode: u32) -> io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(path, fs::Permissions::from_mode(mode))
+}
+
+#[cfg(not(unix))]
+pub(crate) fn restrict(_path: &Path, _mode: u32) -> io::Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SESSION: &str = "53616c7465645f5f0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
+
+    #[test]
+    fn test_parse_name() {
+        assert_eq!(parse_name(" alice ").unwrap(), "alice");
+        assert_eq!(parse_name("bob_2-work").unwrap(), "bob_2-work");
+        assert!(parse_name("2021").is_err());
+        assert!(parse_name("").is_err());
+        assert!(parse_name("../alice").is_err());
+        assert!(parse_name("a b").is_err());
+    }
+
+    #[test]
+    fn test_parse_session() {
+        assert_eq!(parse_session(SESSION).unwrap(), SESSION);
+        assert_eq!(
+            parse_session(&format!("session={}\n", SESSION)).unwrap(),
+            SESSION
+        );
+        assert!(parse_session(&SESSION[..96]).is_ok());
+        assert!(parse_session(&SESSION[..100]).is_err());
+        assert!(parse_session(&SESSION.replace('a', "z")).is_err());
+        assert!(parse_session("").is_err());
+    }
+
+    #[test]
+    fn test_set() {
+        let active = OnceLock::new();
+        set(&active, Some("alice".into())).unwrap();
+        set(&active, Some("alice".into())).unwrap();
+        let e = set(&active, Some("bob".into())).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "cannot switch to profile \"bob\", profile \"alice\" is active already."
+        );
+        assert!(set(&active, None).is_err());
+        assert_eq!(active.get(), Some(&Some("alice".into())));
+    }
+
+    #[test]
+    fn test_profiles() {
+        let dir = env::temp_dir().join(format!("aoc-profiles-{}", std::process::id()));
+        let profiles = Profiles::new(&dir);
+        assert!(profiles.list().unwrap().is_empty());
+        assert!(matches!(
+            profiles.session("alice"),
+            Err(ProfileError::NotFound(_))
+        ));
+
+        let path = profiles.save("bob", SESSION).unwrap();
+        profiles.save("alice", &SESSION[..96]).unwrap();
+        profiles
+            .save("bob", &format!("session={}", SESSION))
+            .unwrap();
+        assert!(matches!(
+            profiles.save("carol", "abc"),
+            Err(ProfileError::InvalidSession)
+        ));
+
+        assert_eq!(profiles.list().unwrap(), ["alice", "bob"]);
+        assert_eq!(profiles.session("bob").unwrap(), SESSION);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = |path: &Path| fs::metadata(path).unwrap().permissions().mode() & 0o777;
+            assert_eq!(mode(&path), 0o600);
+            assert_eq!(mode(&dir), 0o700);
+        }
+
+        profiles.remove("alice").unwrap();
+        assert!(profiles.remove("alice").is_err());
+        assert_eq!(profiles.list().unwrap(), ["bob"]);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
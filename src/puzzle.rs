@@ -0,0 +1,232 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use crate::profiles;
+use std::{
+    env,
+    fmt::Display,
+    io,
+    path::PathBuf,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Environment variable holding the year used when `--year` is not passed.
+/// Set it in the `[env]` section of `.cargo/config` to make a year the default.
+pub const YEAR_ENV: &str = "AOC_YEAR";
+
+/// A puzzle of a specific year, or of the repository's single-year layout.
+///
+/// Days without a year keep their files in `src/inputs/DD.txt`, `src/bin/DD.rs` and so on.
+/// Days of a year keep them in `src/inputs/YYYY/DD.txt` and `src/bin/YYYY-DD.rs`.
+/// With a profile, its files are kept in `src/inputs/<profile>/DD.txt`, see [`profiles`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Puzzle {
+    pub year: Option<u16>,
+    pub day: u8,
+}
+
+impl Puzzle {
+    pub fn new(year: Option<u16>, day: u8) -> Puzzle {
+        Puzzle { year, day }
+    }
+
+    /// Parses the name of a day binary, e.g. `07` or `2021-07`.
+    pub fn from_bin_name(name: &str) -> Option<Puzzle> {
+        match name.split_once('-') {
+            Some((year, day)) => Some(Puzzle::new(Some(year.parse().ok()?), day.parse().ok()?)),
+            None => Some(Puzzle::new(None, name.parse().ok()?)),
+        }
+    }
+
+    /// Name of the day binary, e.g. `07` or `2021-07`.
+    pub fn bin_name(&self) -> String {
+        match self.year {
+            Some(year) => format!("{}-{:02}", year, self.day),
+            None => format!("{:02}", self.day),
+        }
+    }
+
+    /// Path of this puzzle's file in `src/<folder>`, relative to the crate root.
+    pub fn relative_path(&self, folder: &str, extension: &str) -> PathBuf {
+        let mut path = PathBuf::from("src").join(folder);
+        if let Some(profile) = profiles::active() {
+            if profiles::PROFILE_FOLDERS.contains(&folder) {
+                path.push(profile);
+            }
+        }
+        if let Some(year) = self.year {
+            path.push(year.to_string());
+        }
+        path.join(format!("{:02}.{}", self.day, extension))
+    }
+
+    /// Path of this puzzle's file in `src/<folder>`.
+    pub fn path(&self, folder: &str, extension: &str) -> io::Result<PathBuf> {
+        Ok(env::current_dir()?.join(self.relative_path(folder, extension)))
+    }
+}
+
+impl From<u8> for Puzzle {
+    fn from(day: u8) -> Puzzle {
+        Puzzle::new(None, day)
+    }
+}
+
+impl From<(u16, u8)> for Puzzle {
+    fn from((year, day): (u16, u8)) -> Puzzle {
+        Puzzle::new(Some(year), day)
+    }
+}
+
+impl Display for Puzzle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.year {
+            Some(year) => write!(f, "day {:02} of {}", self.day, year),
+            None => write!(f, "day {:02}", self.day),
+        }
+    }
+}
+
+/// Parses a year of Advent of Code, e.g. `2021`.
+pub fn parse_year(val: &str) -> Result<u16, String> {
+    match val.trim().parse::<u16>() {
+        Ok(year) if year >= 2015 => Ok(year),
+        _ => Err(format!(
+            "\"{}\" is not a year of Advent of Code",
+            val.trim()
+        )),
+    }
+}
+
+/// Returns the year passed with `--year`, falling back to `AOC_YEAR`.
+pub fn year_or_default(year: Option<u16>) -> Result<Option<u16>, String> {
+    if year.is_some() {
+        return Ok(year);
+    }
+
+    match env::var(YEAR_ENV) {
+        Ok(val) if !val.trim().is_empty() => parse_year(&val)
+            .map(Some)
+            .map_err(|e| format!("{}: {}", YEAR_ENV, e)),
+        _ => Ok(None),
+    }
+}
+
+/// Offset of the timezone puzzles unlock in (EST, UTC-5), in seconds.
+pub const UNLOCK_OFFSET: i64 = -5 * 60 * 60;
+
+/// Returns the year of the current event at `now`. From November on, this is the upcoming event,
+/// so its days can be waited for. Before, it is the one of the previous December.
+pub fn event_year(now: SystemTime) -> u16 {
+    let secs = match now.duration_since(UNIX_EPOCH) {
+        Ok(elapsed) => elapsed.as_secs() as i64,
+        Err(e) => -(e.duration().as_secs() as i64),
+    };
+    let (year, month, _) = civil_from_days((secs + UNLOCK_OFFSET).div_euclid(86_400));
+    if month >= 11 {
+        year as u16
+    } else {
+        (year - 1) as u16
+    }
+}
+
+/// Returns the moment a puzzle unlocks: midnight EST of its day in December.
+pub fn unlock_time(year: u16, day: u8) -> SystemTime {
+    let days = days_from_civil(i64::from(year), 12, u32::from(day));
+    let secs = days * 86_400 - UNLOCK_OFFSET;
+    UNIX_EPOCH + Duration::from_secs(secs.max(0) as u64)
+}
+
+/// Converts a `(year, month, day)` date to days since the unix epoch.
+/// See <http://howardhinnant.github.io/date_algorithms.html#days_from_civil>.
+pub fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = year - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let mp = i64::from((month + 9) % 12);
+    let doy = (153 * mp + 2) / 5 + i64::from(day) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Converts days since the unix epoch to a `(year, month, day)` date.
+/// See <http://howardhinnant.github.io/date_algorithms.html#civil_from_days>.
+pub fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bin_name() {
+        assert_eq!(Puzzle::from(7).bin_name(), "07");
+        assert_eq!(Puzzle::from((2021, 7)).bin_name(), "2021-07");
+
+        assert_eq!(Puzzle::from_bin_name("07"), Some(Puzzle::from(7)));
+        assert_eq!(
+            Puzzle::from_bin_name("2021-07"),
+            Some(Puzzle::from((2021, 7)))
+        );
+        assert_eq!(Puzzle::from_bin_name("advent_of_code"), None);
+        assert_eq!(Puzzle::from_bin_name("2021-x"), None);
+    }
+
+    #[test]
+    fn test_relative_path() {
+        assert_eq!(
+            Puzzle::from(7).relative_path("inputs", "txt"),
+            PathBuf::from("src/inputs/07.txt")
+        );
+        assert_eq!(
+            Puzzle::from((2021, 7)).relative_path("puzzles", "md"),
+            PathBuf::from("src/puzzles/2021/07.md")
+        );
+    }
+
+    #[test]
+    fn test_event_year() {
+        let at = |secs: u64| UNIX_EPOCH + Duration::from_secs(secs);
+
+        // 2022-11-01 04:59:59 UTC is still October 31st in EST.
+        assert_eq!(event_year(at(1_667_278_799)), 2021);
+        assert_eq!(event_year(at(1_667_278_800)), 2022);
+        // 2022-12-25
+        assert_eq!(event_year(at(1_671_926_400)), 2022);
+        // 2023-06-15
+        assert_eq!(event_year(at(1_686_787_200)), 2022);
+    }
+
+    #[test]
+    fn test_unlock_time() {
+        // 2022-12-01 05:00:00 UTC
+        assert_eq!(
+            unlock_time(2022, 1),
+            UNIX_EPOCH + Duration::from_secs(1_669_870_800)
+        );
+        assert_eq!(
+            unlock_time(2022, 25),
+            UNIX_EPOCH + Duration::from_secs(1_669_870_800 + 24 * 86_400)
+        );
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(civil_from_days(days_from_civil(2024, 2, 29)), (2024, 2, 29));
+    }
+
+    #[test]
+    fn test_parse_year() {
+        assert_eq!(parse_year("2021"), Ok(2021));
+        assert!(parse_year("21").is_err());
+        assert!(parse_year("next").is_err());
+    }
+}
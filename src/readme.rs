@@ -0,0 +1,248 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use crate::{
+    puzzle::Puzzle,
+    results::{ParseResult, PartResult},
+};
+use std::{fmt::Display, fs, io, time::Duration};
+
+pub const README_PATH: &str = "README.md";
+pub const TABLE_START: &str = "<!--- benchmarking table --->";
+pub const TABLE_END: &str = "<!--- benchmarking table end --->";
+
+#[derive(Debug)]
+pub enum ReadmeError {
+    IoError(io::Error),
+    MarkerNotFound(String),
+}
+
+impl Display for ReadmeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReadmeError::IoError(e) => write!(f, "could not update {}: {}", README_PATH, e),
+            ReadmeError::MarkerNotFound(marker) => {
+                write!(f, "could not find \"{}\" in {}.", marker, README_PATH)
+            }
+        }
+    }
+}
+
+/// Markers around the table of `year`. Days without a year use [`TABLE_START`] and [`TABLE_END`],
+/// days of a year get a table of their own, e.g. `<!--- benchmarking table 2021 --->`.
+pub fn markers(year: Option<u16>) -> (String, String) {
+    match year {
+        Some(year) => (
+            format!("<!--- benchmarking table {} --->", year),
+            format!("<!--- benchmarking table {} end --->", year),
+        ),
+        None => (TABLE_START.into(), TABLE_END.into()),
+    }
+}
+
+/// Renders `results` of the days of `year` as a Markdown table with one row per day.
+/// The parse steps in `parses` get a column of their own and count towards the total.
+pub fn render_table(year: Option<u16>, parses: &[ParseResult], results: &[PartResult]) -> String {
+    let mut days: Vec<u8> = results
+        .iter()
+        .map(|r| r.day)
+        .chain(parses.iter().map(|p| p.day))
+        .collect();
+    days.sort_unstable();
+    days.dedup();
+
+    let format = |elapsed: Option<Duration>| {
+        elapsed
+            .map(|elapsed| format!("`{:.2?}`", elapsed))
+            .unwrap_or_else(|| "-".into())
+    };
+    let parse = |day: u8| format(parses.iter().find(|p| p.day == day).map(|p| p.elapsed));
+    let timing = |day: u8, part: u8| {
+        format(
+            results
+                .iter()
+                .find(|r| r.day == day && r.part == part && r.is_solved())
+                .map(|r| r.elapsed),
+        )
+    };
+
+    let mut table = match year {
+        Some(year) => format!("## Benchmarks {}\n\n", year),
+        None => String::from("## Benchmarks\n\n"),
+    };
+    table.push_str("| Day | Parse | Part 1 | Part 2 |\n");
+    table.push_str("| :---: | :---: | :---: | :---: |\n");
+
+    for day in days {
+        table.push_str(&format!(
+            "| [Day {}](./src/bin/{}.rs) | {} | {} | {} |\n",
+            day,
+            Puzzle::new(year, day).bin_name(),
+            parse(day),
+            timing(day, 1),
+            timing(day, 2)
+        ));
+    }
+
+    let total: Duration = parses.iter().map(|p| p.elapsed).sum::<Duration>()
+        + results
+            .iter()
+            .filter(|r| r.is_solved())
+            .map(|r| r.elapsed)
+            .sum::<Duration>();
+    table.push_str(&format!(
+        "\n**Total: {:.2}ms**\n",
+        total.as_secs_f64() * 1000_f64
+    ));
+
+    table
+}
+
+/// Replaces everything between the markers of `year` in `readme` with `table`.
+/// The first table of a year is added below the table of the days without a year.
+pub fn replace_table(readme: &str, year: Option<u16>, table: &str) -> Result<String, ReadmeError> {
+    let (table_start, table_end) = markers(year);
+    let start = match readme.find(&table_start) {
+        Some(start) => start + table_start.len(),
+        None if year.is_some() => {
+            let end = readme
+                .find(TABLE_END)
+                .ok_or_else(|| ReadmeError::MarkerNotFound(table_start.clone()))?
+                + TABLE_END.len();
+            return Ok(format!(
+                "{}\n{}\n\n{}\n{}{}",
+                &readme[..end],
+                table_start,
+                table,
+                table_end,
+                &readme[end..]
+            ));
+        }
+        None => return Err(ReadmeError::MarkerNotFound(table_start)),
+    };
+    let end = start
+        + readme[start..]
+            .find(&table_end)
+            .ok_or(ReadmeError::MarkerNotFound(table_end))?;
+
+    Ok(format!(
+        "{}\n\n{}\n{}",
+        &readme[..start],
+        table,
+        &readme[end..]
+    ))
+}
+
+/// Writes a benchmark table for `parses` and `results` to the delimited section of `README.md`.
+pub fn update(
+    year: Option<u16>,
+    parses: &[ParseResult],
+    results: &[PartResult],
+) -> Result<(), ReadmeError> {
+    let readme = fs::read_to_string(README_PATH).map_err(ReadmeError::IoError)?;
+    let updated = replace_table(&readme, year, &render_table(year, parses, results))?;
+    fs::write(README_PATH, updated).map_err(ReadmeError::IoError)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{answers::Verdict, results::Outcome};
+
+    fn result(day: u8, part: u8, micros: Option<u64>) -> PartResult {
+        PartResult {
+            year: None,
+            day,
+            part,
+            outcome: match micros {
+                Some(_) => Outcome::Solved("0".into()),
+                None => Outcome::Unsolved,
+            },
+            elapsed: Duration::from_micros(micros.unwrap_or(0)),
+            verdict: Verdict::Unverified,
+            memory: None,
+        }
+    }
+
+    #[test]
+    fn test_render_table() {
+        let parse = ParseResult {
+            year: None,
+            day: 7,
+            elapsed: Duration::from_micros(500),
+            memory: None,
+        };
+        let table = render_table(
+            None,
+            &[parse],
+            &[
+                result(1, 1, Some(100)),
+                result(1, 2, Some(1500)),
+                result(7, 1, Some(200)),
+                result(7, 2, Some(300)),
+                result(9, 1, Some(400)),
+                result(9, 2, None),
+            ],
+        );
+
+        assert_eq!(
+            table,
+            "## Benchmarks\n\n\
+             | Day | Parse | Part 1 | Part 2 |\n\
+             | :---: | :---: | :---: | :---: |\n\
+             | [Day 1](./src/bin/01.rs) | - | `100.00µs` | `1.50ms` |\n\
+             | [Day 7](./src/bin/07.rs) | `500.00µs` | `200.00µs` | `300.00µs` |\n\
+             | [Day 9](./src/bin/09.rs) | - | `400.00µs` | - |\n\
+             \n\
+             **Total: 3.00ms**\n"
+        );
+    }
+
+    #[test]
+    fn test_replace_table() {
+        let readme = format!(
+            "# Title\n{}\nold table\n{}\n## Rest\n",
+            TABLE_START, TABLE_END
+        );
+        let replaced = replace_table(&readme, None, "new table\n").unwrap();
+        assert_eq!(
+            replaced,
+            format!(
+                "# Title\n{}\n\nnew table\n\n{}\n## Rest\n",
+                TABLE_START, TABLE_END
+            )
+        );
+
+        assert!(matches!(
+            replace_table("# Title\n", None, "new table\n"),
+            Err(ReadmeError::MarkerNotFound(marker)) if marker == TABLE_START
+        ));
+    }
+
+    #[test]
+    fn test_replace_table_of_year() {
+        let readme = format!(
+            "# Title\n{}\nold table\n{}\n## Rest\n",
+            TABLE_START, TABLE_END
+        );
+        let (start, end) = markers(Some(2021));
+
+        // the first table of a year is added, later ones replace it. Other tables are kept.
+        let added = replace_table(&readme, Some(2021), "2021 table\n").unwrap();
+        assert_eq!(
+            added,
+            format!(
+                "# Title\n{}\nold table\n{}\n{}\n\n2021 table\n\n{}\n## Rest\n",
+                TABLE_START, TABLE_END, start, end
+            )
+        );
+        let replaced = replace_table(&added, Some(2021), "new 2021 table\n").unwrap();
+        assert_eq!(replaced, added.replace("2021 table", "new 2021 table"));
+
+        assert!(matches!(
+            replace_table("# Title\n", Some(2021), "2021 table\n"),
+            Err(ReadmeError::MarkerNotFound(marker)) if marker == start
+        ));
+    }
+}
@@ -0,0 +1,537 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use crate::{answers::Verdict, memory::AllocStats};
+use std::{
+    env,
+    error::Error,
+    fmt::Display,
+    fs::OpenOptions,
+    io::{self, Write},
+    time::Duration,
+};
+
+/// Environment variable pointing to a file that `solve!` appends one JSON record per part to.
+pub const RESULTS_ENV: &str = "AOC_RESULTS";
+
+/// What running a part produced.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Solved(String),
+    Unsolved,
+    /// The part panicked or returned an error. Holds the panic message and location,
+    /// or the error and its sources.
+    Errored(String),
+    /// The part exceeded `--timeout` or `--day-timeout`.
+    TimedOut,
+}
+
+impl Outcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Outcome::Solved(_) => "solved",
+            Outcome::Unsolved => "unsolved",
+            Outcome::Errored(_) => "errored",
+            Outcome::TimedOut => "timed_out",
+        }
+    }
+}
+
+/// Error that a part returning `Result` can use to report that it is not solved yet.
+///
+/// ```ignore
+/// pub fn part_one(input: &str) -> Result<u32, Box<dyn Error>> {
+///     Err(Unsolved.into())
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Unsolved;
+
+impl Display for Unsolved {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "not solved")
+    }
+}
+
+impl Error for Unsolved {}
+
+/// Return types accepted from a part: `Option<T>` and `Result<T, E>`.
+pub trait IntoOutcome {
+    fn into_outcome(self) -> Outcome;
+}
+
+impl IntoOutcome for Outcome {
+    fn into_outcome(self) -> Outcome {
+        self
+    }
+}
+
+impl<T: Display> IntoOutcome for Option<T> {
+    fn into_outcome(self) -> Outcome {
+        match self {
+            Some(answer) => Outcome::Solved(answer.to_string()),
+            None => Outcome::Unsolved,
+        }
+    }
+}
+
+/// An error is reported with its chain of sources, e.g. `invalid move "X 1": unknown direction`.
+impl<T: Display, E: Into<Box<dyn Error>>> IntoOutcome for Result<T, E> {
+    fn into_outcome(self) -> Outcome {
+        let error = match self {
+            Ok(answer) => return Outcome::Solved(answer.to_string()),
+            Err(error) => error.into(),
+        };
+
+        if error.is::<Unsolved>() {
+            return Outcome::Unsolved;
+        }
+
+        let mut message = error.to_string();
+        let mut source = error.source();
+        while let Some(error) = source {
+            message.push_str(&format!(": {}", error));
+            source = error.source();
+        }
+        Outcome::Errored(message)
+    }
+}
+
+/// Structured result of running one part of a day's solution.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartResult {
+    /// `None` for days of the repository's single-year layout.
+    pub year: Option<u16>,
+    pub day: u8,
+    pub part: u8,
+    pub outcome: Outcome,
+    pub elapsed: Duration,
+    pub verdict: Verdict,
+    /// Memory allocated by the part, if allocations are counted.
+    pub memory: Option<AllocStats>,
+}
+
+/// Timing of the parse step of a [`Solution`](crate::solution::Solution).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseResult {
+    pub year: Option<u16>,
+    pub day: u8,
+    pub elapsed: Duration,
+    pub memory: Option<AllocStats>,
+}
+
+/// Results of all parts of a day that ran, and of its parse step if it has one.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DayResult {
+    pub parse: Option<ParseResult>,
+    pub parts: Vec<PartResult>,
+    /// Why the day did not run, e.g. its input is encrypted and there is no key.
+    pub skipped: Option<String>,
+}
+
+impl PartResult {
+    pub fn answer(&self) -> Option<&str> {
+        match &self.outcome {
+            Outcome::Solved(answer) => Some(answer),
+            _ => None,
+        }
+    }
+
+    pub fn error(&self) -> Option<&str> {
+        match &self.outcome {
+            Outcome::Errored(error) => Some(error),
+            _ => None,
+        }
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.answer().is_some()
+    }
+
+    pub fn is_errored(&self) -> bool {
+        self.error().is_some()
+    }
+
+    pub fn is_timed_out(&self) -> bool {
+        self.outcome == Outcome::TimedOut
+    }
+
+    /// Serializes the result as a single JSON Lines record, e.g.
+    /// `{"year":2021,"day":1,"part":1,"answer":"24000","elapsed_ns":12345,"solved":true,"status":"solved","error":null,"verdict":"correct","expected":"24000","allocations":null,"allocated_bytes":null,"peak_bytes":null}`.
+    pub fn to_json(&self) -> String {
+        let expected = match &self.verdict {
+            Verdict::Unverified => None,
+            Verdict::Correct => self.answer(),
+            Verdict::Wrong { expected } => Some(expected.as_str()),
+        };
+
+        format!(
+            "{{\"year\":{},\"day\":{},\"part\":{},\"answer\":{},\"elapsed_ns\":{},\"solved\":{},\"status\":\"{}\",\"error\":{},\"verdict\":\"{}\",\"expected\":{},\"allocations\":{},\"allocated_bytes\":{},\"peak_bytes\":{}}}",
+            json_number_or_null(self.year.map(u64::from)),
+            self.day,
+            self.part,
+            json_string_or_null(self.answer()),
+            self.elapsed.as_nanos(),
+            self.is_solved(),
+            self.outcome.as_str(),
+            json_string_or_null(self.error()),
+            self.verdict.as_str(),
+            json_string_or_null(expected),
+            json_number_or_null(self.memory.map(|m| m.allocations)),
+            json_number_or_null(self.memory.map(|m| m.allocated)),
+            json_number_or_null(self.memory.map(|m| m.peak)),
+        )
+    }
+
+    /// Parses a record previously written by [`PartResult::to_json`].
+    pub fn from_json(line: &str) -> Option<PartResult> {
+        let fields = parse_flat_object(line)?;
+        let get = |key: &str| fields.iter().find(|(k, _)| k == key).map(|(_, v)| v);
+
+        let string_or_null = |key: &str| match get(key) {
+            Some(JsonValue::String(val)) => Some(Some(val.clone())),
+            Some(JsonValue::Null) => Some(None),
+            _ => None,
+        };
+
+        // records written before errors were reported have no status.
+        let outcome = match (string_or_null("answer")?, string_or_null("error").flatten()) {
+            (Some(answer), _) => Outcome::Solved(answer),
+            (None, Some(error)) => Outcome::Errored(error),
+            (None, None) => match get("status") {
+                Some(JsonValue::String(status)) if status == "timed_out" => Outcome::TimedOut,
+                _ => Outcome::Unsolved,
+            },
+        };
+
+        // records written before answers were verified have no verdict.
+        let verdict = match (get("verdict"), string_or_null("expected").flatten()) {
+            (Some(JsonValue::String(v)), _) if v == "correct" => Verdict::Correct,
+            (Some(JsonValue::String(v)), Some(expected)) if v == "wrong" => {
+                Verdict::Wrong { expected }
+            }
+            _ => Verdict::Unverified,
+        };
+
+        // records written without counting allocations have no memory fields.
+        let memory = match (
+            get("allocations").and_then(JsonValue::as_number),
+            get("allocated_bytes").and_then(JsonValue::as_number),
+            get("peak_bytes").and_then(JsonValue::as_number),
+        ) {
+            (Some(allocations), Some(allocated), Some(peak)) => Some(AllocStats {
+                allocations,
+                allocated,
+                peak,
+            }),
+            _ => None,
+        };
+
+        // records written before years were supported have no year.
+        let year = get("year")
+            .and_then(JsonValue::as_number)
+            .map(u16::try_from)
+            .transpose()
+            .ok()?;
+
+        Some(PartResult {
+            year,
+            day: get("day")?.as_number()?.try_into().ok()?,
+            part: get("part")?.as_number()?.try_into().ok()?,
+            outcome,
+            elapsed: Duration::from_nanos(get("elapsed_ns")?.as_number()?),
+            verdict,
+            memory,
+        })
+    }
+}
+
+/// Appends `result` to the file named by `AOC_RESULTS`, if that variable is set.
+pub fn record(result: &PartResult) -> io::Result<()> {
+    let path = match env::var_os(RESULTS_ENV) {
+        Some(path) => path,
+        None => return Ok(()),
+    };
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", result.to_json())
+}
+
+/// Parses every valid record of a JSON Lines document, skipping lines that are not results.
+pub fn parse_records(contents: &str) -> Vec<PartResult> {
+    contents.lines().filter_map(PartResult::from_json).collect()
+}
+
+pub(crate) fn json_string_or_null(val: Option<&str>) -> String {
+    match val {
+        Some(val) => format!("\"{}\"", escape_json(val)),
+        None => "null".into(),
+    }
+}
+
+pub(crate) fn json_number_or_null(val: Option<u64>) -> String {
+    match val {
+        Some(val) => val.to_string(),
+        None => "null".into(),
+    }
+}
+
+fn escape_json(val: &str) -> String {
+    let mut escaped = String::with_capacity(val.len());
+    for c in val.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum JsonValue {
+    String(String),
+    Number(u64),
+    Bool(bool),
+    Null,
+}
+
+impl JsonValue {
+    pub(crate) fn as_number(&self) -> Option<u64> {
+        match self {
+            JsonValue::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+}
+
+/// Minimal parser for the flat objects emitted by `to_json`: no nesting, no floats.
+pub(crate) fn parse_flat_object(line: &str) -> Option<Vec<(String, JsonValue)>> {
+    let mut chars = line.trim().chars().peekable();
+    let mut fields = vec![];
+
+    if chars.next()? != '{' {
+        return None;
+    }
+
+    loop {
+        skip_whitespace(&mut chars);
+        match chars.next()? {
+            '}' if fields.is_empty() => break,
+            '"' => {}
+            _ => return None,
+        }
+
+        let key = parse_string(&mut chars)?;
+        skip_whitespace(&mut chars);
+        if chars.next()? != ':' {
+            return None;
+        }
+        skip_whitespace(&mut chars);
+
+        let value = match chars.peek()? {
+            '"' => {
+                chars.next();
+                JsonValue::String(parse_string(&mut chars)?)
+            }
+            c if c.is_ascii_digit() => {
+                let mut digits = String::new();
+                while let Some(c) = chars.peek().filter(|c| c.is_ascii_digit()) {
+                    digits.push(*c);
+                    chars.next();
+                }
+                JsonValue::Number(digits.parse().ok()?)
+            }
+            _ => {
+                let word: String =
+                    std::iter::from_fn(|| chars.next_if(|c| c.is_alphabetic())).collect();
+                match word.as_str() {
+                    "true" => JsonValue::Bool(true),
+                    "false" => JsonValue::Bool(false),
+                    "null" => JsonValue::Null,
+                    _ => return None,
+                }
+            }
+        };
+
+        fields.push((key, value));
+
+        skip_whitespace(&mut chars);
+        match chars.next()? {
+            ',' => continue,
+            '}' => break,
+            _ => return None,
+        }
+    }
+
+    Some(fields)
+}
+
+fn skip_whitespace(chars: &mut std::iter::Peekable<std::str::Chars>) {
+    while chars.next_if(|c| c.is_whitespace()).is_some() {}
+}
+
+/// Parses the remainder of a string literal whose opening quote was already consumed.
+fn parse_string(chars: &mut std::iter::Peekable<std::str::Chars>) -> Option<String> {
+    let mut val = String::new();
+    loop {
+        match chars.next()? {
+            '"' => return Some(val),
+            '\\' => match chars.next()? {
+                'n' => val.push('\n'),
+                'r' => val.push('\r'),
+                't' => val.push('\t'),
+                'u' => {
+                    let code: String = chars.by_ref().take(4).collect();
+                    val.push(char::from_u32(u32::from_str_radix(&code, 16).ok()?)?);
+                }
+                c => val.push(c),
+            },
+            c => val.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_into_outcome() {
+        assert_eq!(Some(42).into_outcome(), Outcome::Solved("42".into()));
+        assert_eq!(None::<u32>.into_outcome(), Outcome::Unsolved);
+        assert_eq!(
+            Ok::<_, Unsolved>("abc").into_outcome(),
+            Outcome::Solved("abc".into())
+        );
+        assert_eq!(Err::<u32, _>(Unsolved).into_outcome(), Outcome::Unsolved);
+        assert_eq!(
+            Err::<u32, _>("no start position").into_outcome(),
+            Outcome::Errored("no start position".into())
+        );
+
+        #[derive(Debug)]
+        struct ParseError(std::num::ParseIntError);
+
+        impl Display for ParseError {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "invalid line")
+            }
+        }
+
+        impl Error for ParseError {
+            fn source(&self) -> Option<&(dyn Error + 'static)> {
+                Some(&self.0)
+            }
+        }
+
+        let error = ParseError("x".parse::<u32>().unwrap_err());
+        assert_eq!(
+            Err::<u32, _>(error).into_outcome(),
+            Outcome::Errored("invalid line: invalid digit found in string".into())
+        );
+    }
+
+    #[test]
+    fn test_to_json() {
+        let result = PartResult {
+            year: None,
+            day: 1,
+            part: 2,
+            outcome: Outcome::Solved("45000".into()),
+            elapsed: Duration::from_nanos(74),
+            verdict: Verdict::Correct,
+            memory: None,
+        };
+        assert_eq!(
+            result.to_json(),
+            r#"{"year":null,"day":1,"part":2,"answer":"45000","elapsed_ns":74,"solved":true,"status":"solved","error":null,"verdict":"correct","expected":"45000","allocations":null,"allocated_bytes":null,"peak_bytes":null}"#
+        );
+
+        let result = PartResult {
+            outcome: Outcome::Unsolved,
+            verdict: Verdict::Unverified,
+            ..result
+        };
+        assert_eq!(
+            result.to_json(),
+            r#"{"year":null,"day":1,"part":2,"answer":null,"elapsed_ns":74,"solved":false,"status":"unsolved","error":null,"verdict":"unverified","expected":null,"allocations":null,"allocated_bytes":null,"peak_bytes":null}"#
+        );
+
+        let result = PartResult {
+            outcome: Outcome::Errored("not implemented at src/bin/02.rs:13:15".into()),
+            ..result
+        };
+        assert_eq!(
+            result.to_json(),
+            r#"{"year":null,"day":1,"part":2,"answer":null,"elapsed_ns":74,"solved":false,"status":"errored","error":"not implemented at src/bin/02.rs:13:15","verdict":"unverified","expected":null,"allocations":null,"allocated_bytes":null,"peak_bytes":null}"#
+        );
+
+        let result = PartResult {
+            year: Some(2021),
+            ..result
+        };
+        assert!(result
+            .to_json()
+            .starts_with(r#"{"year":2021,"day":1,"part":2,"#));
+    }
+
+    #[test]
+    fn test_json_roundtrip() {
+        let result = PartResult {
+            year: Some(2021),
+            day: 5,
+            part: 1,
+            outcome: Outcome::Solved("C\"M\\Z\n\u{1}".into()),
+            elapsed: Duration::from_secs(10) + Duration::from_nanos(3),
+            verdict: Verdict::Wrong {
+                expected: "MCD".into(),
+            },
+            memory: Some(AllocStats {
+                allocations: 12,
+                allocated: 4096,
+                peak: 1024,
+            }),
+        };
+        assert_eq!(
+            PartResult::from_json(&result.to_json()),
+            Some(result.clone())
+        );
+
+        let result = PartResult {
+            outcome: Outcome::Errored("index out of bounds".into()),
+            ..result
+        };
+        assert_eq!(
+            PartResult::from_json(&result.to_json()),
+            Some(result.clone())
+        );
+
+        let result = PartResult {
+            outcome: Outcome::TimedOut,
+            ..result
+        };
+        assert_eq!(PartResult::from_json(&result.to_json()), Some(result));
+    }
+
+    #[test]
+    fn test_parse_records() {
+        let records = parse_records(
+            "🎄 Part 1 🎄\n\
+             {\"day\":9,\"part\":1,\"answer\":\"88\",\"elapsed_ns\":1500,\"solved\":true}\n\
+             (elapsed: 1.00ms)\n\
+             { \"day\": 9, \"part\": 2, \"answer\": null, \"elapsed_ns\": 20, \"solved\": false }",
+        );
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].answer(), Some("88"));
+        assert_eq!(records[1].elapsed, Duration::from_nanos(20));
+        assert!(!records[1].is_solved());
+        assert_eq!(records[1].verdict, Verdict::Unverified);
+        assert_eq!(records[1].year, None);
+    }
+}
@@ -0,0 +1,618 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use crate::{
+    answers::{Answers, Verdict},
+    args::RunArgs,
+    baseline::Baseline,
+    bench::{self, Stats},
+    crypt,
+    days::Day,
+    memory::{self, AllocStats},
+    puzzle::Puzzle,
+    results::{self, DayResult, IntoOutcome, Outcome, ParseResult, PartResult},
+    solution::Solution,
+    ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
+use std::{
+    cell::{Cell, RefCell},
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        mpsc, Arc, Mutex, Once,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+/// The last part of a day. `solve!` exits with a failure status after it if any part failed.
+pub const LAST_PART: u8 = 2;
+
+/// Stack size of the threads that run parts with a time limit, matching the usual main thread.
+const STACK_SIZE: usize = 8 * 1024 * 1024;
+
+static FAILED: AtomicBool = AtomicBool::new(false);
+static TIMED_OUT: AtomicBool = AtomicBool::new(false);
+
+/// Threads running a part with a time limit. Outside of `run_with_limit`, these are parts
+/// that timed out and keep running in the background.
+static RUNNING: AtomicUsize = AtomicUsize::new(0);
+
+/// Wall time spent on the parts of the current day, counted against `--day-timeout`.
+static DAY_SPENT: Mutex<Duration> = Mutex::new(Duration::ZERO);
+
+thread_local! {
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+    static PANIC_MESSAGE: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Whether any part run by this process has errored or timed out.
+pub fn has_failed() -> bool {
+    FAILED.load(Ordering::Relaxed)
+}
+
+/// Whether any part run by this process has timed out. `solve!` skips the remaining parts then,
+/// like `cargo all` does for the day.
+pub fn has_timed_out() -> bool {
+    TIMED_OUT.load(Ordering::Relaxed)
+}
+
+/// Number of parts that timed out and are still running. They compete for the CPU and add to the
+/// allocation counters, so timings taken meanwhile are unreliable.
+pub fn stray_parts() -> usize {
+    RUNNING.load(Ordering::SeqCst)
+}
+
+/// Runs `func`, turning a panic into an error holding the panic message and location.
+/// The default panic output is suppressed while `func` runs.
+fn catch_panic<T>(func: impl FnOnce() -> T) -> Result<T, String> {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !CATCHING.get() {
+                return default_hook(info);
+            }
+
+            let payload = info.payload();
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "Box<dyn Any>".into());
+            let message = match info.location() {
+                Some(location) => format!("{} at {}", message, location),
+                None => message,
+            };
+            PANIC_MESSAGE.set(Some(message));
+        }));
+    });
+
+    CATCHING.set(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(func));
+    CATCHING.set(false);
+
+    result.map_err(|_| {
+        PANIC_MESSAGE
+            .take()
+            .unwrap_or_else(|| "unknown panic".into())
+    })
+}
+
+/// Output of running a part or a parse step, with its timing and memory use.
+struct Execution<T> {
+    value: T,
+    elapsed: Duration,
+    stats: Option<Stats>,
+    memory: Option<AllocStats>,
+    /// Whether a timed out part was still running meanwhile.
+    contended: bool,
+}
+
+impl<T> Execution<T> {
+    fn map<U>(self, func: impl FnOnce(T) -> U) -> Execution<U> {
+        Execution {
+            value: func(self.value),
+            elapsed: self.elapsed,
+            stats: self.stats,
+            memory: self.memory,
+            contended: self.contended,
+        }
+    }
+
+    /// Describes the timing and memory use, e.g. `elapsed: 1.02ms`.
+    fn timing(&self) -> String {
+        let mut timing = match &self.stats {
+            Some(stats) => stats.to_string(),
+            None => format!("elapsed: {:.2?}", self.elapsed),
+        };
+        if let Some(memory) = self.memory {
+            timing.push_str(&format!(", {}", memory));
+        }
+        if self.contended {
+            timing.push_str(", unreliable: a timed out part was still running");
+        }
+        timing
+    }
+}
+
+impl Execution<Outcome> {
+    fn failed(outcome: Outcome, elapsed: Duration) -> Execution<Outcome> {
+        Execution {
+            value: outcome,
+            elapsed,
+            stats: None,
+            memory: None,
+            contended: false,
+        }
+    }
+}
+
+/// Runs `func` once, or repeatedly with `bench`. Allocations are counted for a single run.
+fn execute<T>(func: impl Fn() -> T, bench: bool) -> Execution<T> {
+    if bench {
+        let (_, memory) = memory::track(&func);
+        let (value, stats) = bench::measure(&func);
+        Execution {
+            value,
+            elapsed: stats.median,
+            stats: Some(stats),
+            memory,
+            contended: false,
+        }
+    } else {
+        let timer = Instant::now();
+        let (value, memory) = memory::track(func);
+        let elapsed = timer.elapsed();
+        Execution {
+            value,
+            elapsed,
+            stats: None,
+            memory,
+            contended: false,
+        }
+    }
+}
+
+/// Runs `func` on its own thread and gives up waiting for it after `limit`.
+/// Returns `None` on timeout. The thread cannot be stopped and keeps running in the background,
+/// counted by [`stray_parts`] until it finishes.
+fn run_with_limit<T: Send + 'static>(
+    func: impl FnOnce() -> T + Send + 'static,
+    limit: Duration,
+) -> Option<Result<T, String>> {
+    let (sender, receiver) = mpsc::channel();
+
+    RUNNING.fetch_add(1, Ordering::SeqCst);
+    let spawned = thread::Builder::new()
+        .stack_size(STACK_SIZE)
+        .spawn(move || {
+            let result = catch_panic(func);
+            // counted down before sending, so a part that finished in time is never counted as stray.
+            RUNNING.fetch_sub(1, Ordering::SeqCst);
+            // the receiver is gone if the part timed out, so there is nobody left to tell.
+            let _ = sender.send(result);
+        });
+
+    if let Err(e) = spawned {
+        RUNNING.fetch_sub(1, Ordering::SeqCst);
+        return Some(Err(format!("could not start part: {}", e)));
+    }
+
+    match receiver.recv_timeout(limit) {
+        Ok(result) => Some(result),
+        Err(mpsc::RecvTimeoutError::Timeout) => None,
+        Err(mpsc::RecvTimeoutError::Disconnected) => Some(Err("part exited unexpectedly".into())),
+    }
+}
+
+/// Time a part may take with `--timeout` and `--day-timeout`, if either is set.
+fn time_limit(args: &RunArgs) -> Option<Duration> {
+    let remaining = args
+        .day_timeout
+        .map(|limit| limit.saturating_sub(*DAY_SPENT.lock().unwrap()));
+
+    match (args.timeout, remaining) {
+        (Some(timeout), Some(remaining)) => Some(timeout.min(remaining)),
+        (timeout, remaining) => timeout.or(remaining),
+    }
+}
+
+/// Runs `func` within the time limits, catching panics.
+/// On failure, returns an errored or timed out outcome with the time spent.
+fn run_guarded<T: Send + 'static>(
+    func: impl FnOnce() -> Execution<T> + Send + 'static,
+    args: &RunArgs,
+) -> Result<Execution<T>, (Outcome, Duration)> {
+    let contended = stray_parts() > 0;
+    let timer = Instant::now();
+    let result = match time_limit(args) {
+        Some(limit) => run_with_limit(func, limit),
+        None => Some(catch_panic(func)),
+    };
+    let wall_time = timer.elapsed();
+    *DAY_SPENT.lock().unwrap() += wall_time;
+
+    match result {
+        Some(Ok(execution)) => Ok(Execution {
+            contended: contended || stray_parts() > 0,
+            ..execution
+        }),
+        Some(Err(message)) => Err((Outcome::Errored(message), wall_time)),
+        None => Err((Outcome::TimedOut, wall_time)),
+    }
+}
+
+/// Times a single part, prints its result and records it to `AOC_RESULTS` if requested.
+/// With `--bench`, the part is run repeatedly and its median time is recorded.
+/// A panic inside the part is caught and reported as an error.
+/// With `--timeout` or `--day-timeout`, a part that exceeds its limit is reported as timed out.
+/// Returns `None` if the part was excluded with `--part`.
+pub fn run_part<T: IntoOutcome>(
+    puzzle: impl Into<Puzzle>,
+    part: u8,
+    func: impl Fn(&str) -> T + Send + 'static,
+    input: &str,
+    args: &RunArgs,
+) -> Option<PartResult> {
+    let input = input.to_string();
+    run_prepared_part(puzzle.into(), part, move || func(&input), args)
+}
+
+/// Like [`run_part`], for a part that already holds its input.
+fn run_prepared_part<T: IntoOutcome>(
+    puzzle: Puzzle,
+    part: u8,
+    func: impl Fn() -> T + Send + 'static,
+    args: &RunArgs,
+) -> Option<PartResult> {
+    if !args.includes_part(part) {
+        return None;
+    }
+
+    println!("🎄 {}Part {}{} 🎄", ANSI_BOLD, part, ANSI_RESET);
+
+    let bench = args.bench;
+    let execution = run_guarded(
+        move || execute(func, bench).map(IntoOutcome::into_outcome),
+        args,
+    )
+    .unwrap_or_else(|(outcome, elapsed)| Execution::failed(outcome, elapsed));
+
+    Some(finish_part(puzzle, part, execution, args))
+}
+
+/// Runs the parts of a day in order. Once a part times out, the remaining parts are skipped.
+fn run_in_order(parts: [Box<dyn FnOnce() -> Option<PartResult> + '_>; 2]) -> Vec<PartResult> {
+    let mut results = vec![];
+    for part in parts {
+        let result = match part() {
+            Some(result) => result,
+            None => continue,
+        };
+
+        let timed_out = result.is_timed_out();
+        results.push(result);
+        if timed_out {
+            break;
+        }
+    }
+    results
+}
+
+/// Runs both parts of a day written as free `part_one` and `part_two` functions.
+pub fn run_parts<A: IntoOutcome, B: IntoOutcome>(
+    puzzle: Puzzle,
+    input: &str,
+    args: &RunArgs,
+    part_one: impl Fn(&str) -> A + Send + 'static,
+    part_two: impl Fn(&str) -> B + Send + 'static,
+) -> DayResult {
+    DayResult {
+        parse: None,
+        parts: run_in_order([
+            Box::new(|| run_part(puzzle, 1, part_one, input, args)),
+            Box::new(|| run_part(puzzle, 2, part_two, input, args)),
+        ]),
+        skipped: None,
+    }
+}
+
+/// Parses the input of a [`Solution`] once and runs both parts against it.
+/// The parse step is timed separately from the parts. If it fails, both parts are reported as errored.
+pub fn run_solution<S: Solution>(
+    puzzle: impl Into<Puzzle>,
+    input: &str,
+    args: &RunArgs,
+) -> DayResult {
+    let puzzle = puzzle.into();
+    println!("🎄 {}Parse{} 🎄", ANSI_BOLD, ANSI_RESET);
+
+    let input = input.to_string();
+    let bench = args.bench;
+    let parsed = run_guarded(move || execute(|| S::parse(&input), bench), args);
+
+    let parsed = match parsed {
+        Ok(execution) => {
+            println!("{}({}){}", ANSI_ITALIC, execution.timing(), ANSI_RESET);
+            execution
+        }
+        Err((outcome, elapsed)) => {
+            print_result(&Execution::failed(outcome, elapsed), &Verdict::Unverified);
+            let parse = ParseResult {
+                year: puzzle.year,
+                day: puzzle.day,
+                elapsed,
+                memory: None,
+            };
+
+            let parts = [1, 2]
+                .into_iter()
+                .filter(|part| args.includes_part(*part))
+                .map(|part| {
+                    println!("🎄 {}Part {}{} 🎄", ANSI_BOLD, part, ANSI_RESET);
+                    let outcome = Outcome::Errored("could not parse input".into());
+                    finish_part(
+                        puzzle,
+                        part,
+                        Execution::failed(outcome, Duration::ZERO),
+                        args,
+                    )
+                })
+                .collect();
+
+            return DayResult {
+                parse: Some(parse),
+                parts,
+                skipped: None,
+            };
+        }
+    };
+
+    let parse = ParseResult {
+        year: puzzle.year,
+        day: puzzle.day,
+        elapsed: parsed.elapsed,
+        memory: parsed.memory,
+    };
+    let parsed = Arc::new(parsed.value);
+    let (one, two) = (Arc::clone(&parsed), parsed);
+
+    DayResult {
+        parse: Some(parse),
+        parts: run_in_order([
+            Box::new(|| run_prepared_part(puzzle, 1, move || S::part_one(&one), args)),
+            Box::new(|| run_prepared_part(puzzle, 2, move || S::part_two(&two), args)),
+        ]),
+        skipped: None,
+    }
+}
+
+/// Checks, prints and records the outcome of a part.
+fn finish_part(
+    puzzle: Puzzle,
+    part: u8,
+    execution: Execution<Outcome>,
+    args: &RunArgs,
+) -> PartResult {
+    if matches!(execution.value, Outcome::Errored(_) | Outcome::TimedOut) {
+        FAILED.store(true, Ordering::Relaxed);
+    }
+    if execution.value == Outcome::TimedOut {
+        TIMED_OUT.store(true, Ordering::Relaxed);
+    }
+
+    let verdict = match Answers::load(puzzle) {
+        Ok(_) if args.custom_input => Verdict::Unverified,
+        Ok(answers) => answers.check(
+            part,
+            match &execution.value {
+                Outcome::Solved(answer) => Some(answer),
+                _ => None,
+            },
+        ),
+        Err(e) => {
+            eprintln!("could not read answers for {}: {}", puzzle, e);
+            Verdict::Unverified
+        }
+    };
+
+    print_result(&execution, &verdict);
+    let contended = execution.contended;
+
+    let result = PartResult {
+        year: puzzle.year,
+        day: puzzle.day,
+        part,
+        outcome: execution.value,
+        elapsed: execution.elapsed,
+        verdict,
+        memory: execution.memory,
+    };
+
+    // timings taken while a timed out part is running would make for a misleading baseline.
+    if (args.compare || args.save_baseline)
+        && !args.custom_input
+        && !result.is_timed_out()
+        && !contended
+    {
+        update_baseline(puzzle.year, &result, args);
+    }
+
+    if let Err(e) = results::record(&result) {
+        eprintln!("could not write to ${}: {}", results::RESULTS_ENV, e);
+    }
+
+    result
+}
+
+fn print_result(execution: &Execution<Outcome>, verdict: &Verdict) {
+    let wrong = match verdict {
+        Verdict::Wrong { .. } => format!(" {}", verdict),
+        _ => String::new(),
+    };
+
+    match &execution.value {
+        Outcome::Solved(answer) => println!(
+            "{} {}({}){} {}",
+            answer,
+            ANSI_ITALIC,
+            execution.timing(),
+            ANSI_RESET,
+            verdict
+        ),
+        Outcome::Unsolved => println!("not solved.{}", wrong),
+        Outcome::Errored(message) => {
+            println!("{}errored:{} {}{}", ANSI_BOLD, ANSI_RESET, message, wrong)
+        }
+        Outcome::TimedOut => println!(
+            "{}timed out{} after {:.2?}.{}",
+            ANSI_BOLD, ANSI_RESET, execution.elapsed, wrong
+        ),
+    }
+}
+
+fn update_baseline(year: Option<u16>, result: &PartResult, args: &RunArgs) {
+    let mut baseline = match Baseline::load(year, &args.baseline) {
+        Ok(baseline) => baseline,
+        Err(e) => {
+            eprintln!("could not read baseline \"{}\": {}", args.baseline, e);
+            return;
+        }
+    };
+
+    if args.compare {
+        match baseline.compare(result) {
+            Some(comparison) => println!(
+                "{}vs. {}: {}{}{}",
+                ANSI_ITALIC,
+                args.baseline,
+                comparison,
+                ANSI_RESET,
+                if comparison.is_regression(args.threshold) {
+                    " ⚠ regression"
+                } else {
+                    ""
+                }
+            ),
+            None => println!(
+                "{}vs. {}: no saved timing{}",
+                ANSI_ITALIC, args.baseline, ANSI_RESET
+            ),
+        }
+    }
+
+    if args.save_baseline && result.is_solved() {
+        baseline.update(result);
+        if let Err(e) = baseline.save(year, &args.baseline) {
+            eprintln!("could not save baseline \"{}\": {}", args.baseline, e);
+        }
+    }
+}
+
+/// Returns `true` if any part got slower than `baseline` by more than `threshold` percent.
+pub fn has_slowdowns(results: &[PartResult], baseline: &Baseline, threshold: f64) -> bool {
+    results
+        .iter()
+        .filter(|r| r.is_solved())
+        .filter_map(|r| baseline.compare(r))
+        .any(|c| c.is_regression(threshold))
+}
+
+/// Returns `true` if any part had a recorded answer that it no longer produces.
+pub fn has_regressions(results: &[PartResult]) -> bool {
+    results
+        .iter()
+        .any(|r| matches!(r.verdict, Verdict::Wrong { .. }))
+}
+
+/// Runs a registered day against its input file.
+/// Returns no results if the input could not be read. If it is encrypted and there is no key,
+/// the day is marked as skipped instead of failing.
+pub fn run_day(day: &Day, args: &RunArgs) -> DayResult {
+    *DAY_SPENT.lock().unwrap() = Duration::ZERO;
+
+    match crate::try_read_file("inputs", day.puzzle) {
+        Ok(input) => (day.run)(&input, args),
+        Err(e) if crypt::is_missing_key(&e) => DayResult {
+            skipped: Some(e.to_string()),
+            ..DayResult::default()
+        },
+        Err(e) => {
+            eprintln!("could not open input file for {}: {}", day.puzzle, e);
+            DayResult::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        type Parsed = Vec<u32>;
+        type PartOne = Option<u32>;
+        type PartTwo = Option<u32>;
+
+        fn parse(input: &str) -> Vec<u32> {
+            input.lines().map(|l| l.parse().unwrap()).collect()
+        }
+
+        fn part_one(numbers: &Vec<u32>) -> Option<u32> {
+            Some(numbers.iter().sum())
+        }
+
+        fn part_two(_: &Vec<u32>) -> Option<u32> {
+            None
+        }
+    }
+
+    #[test]
+    fn test_run_solution() {
+        let result = run_solution::<Sum>(0, "1\n2\n3", &RunArgs::default());
+        assert!(result.parse.is_some());
+        let outcomes: Vec<_> = result.parts.iter().map(|r| &r.outcome).collect();
+        assert_eq!(
+            outcomes,
+            vec![&Outcome::Solved("6".into()), &Outcome::Unsolved]
+        );
+
+        let args = RunArgs {
+            part: Some(2),
+            ..RunArgs::default()
+        };
+        let result = run_solution::<Sum>(0, "1\nx", &args);
+        assert_eq!(result.parts.len(), 1);
+        assert_eq!(
+            result.parts[0].outcome,
+            Outcome::Errored("could not parse input".into())
+        );
+    }
+
+    #[test]
+    fn test_stray_parts() {
+        assert_eq!(run_with_limit(|| 1, Duration::from_secs(5)), Some(Ok(1)));
+        assert_eq!(stray_parts(), 0);
+
+        let slow = || thread::sleep(Duration::from_millis(200));
+        assert_eq!(run_with_limit(slow, Duration::from_millis(10)), None);
+        assert_eq!(stray_parts(), 1);
+        thread::sleep(Duration::from_millis(500));
+        assert_eq!(stray_parts(), 0);
+    }
+
+    #[test]
+    fn test_catch_panic() {
+        assert_eq!(catch_panic(|| 42), Ok(42));
+
+        let message = catch_panic(|| -> u32 { panic!("boom {}", 1) }).unwrap_err();
+        assert!(
+            message.starts_with("boom 1 at src/runner.rs:"),
+            "{}",
+            message
+        );
+    }
+}
@@ -0,0 +1,47 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use crate::results::IntoOutcome;
+
+/// A day's solution that parses its input once and shares it between both parts.
+/// `solve!` and `cargo all` time the parse step and each part separately.
+///
+/// ```ignore
+/// pub struct Day07;
+///
+/// impl Solution for Day07 {
+///     type Parsed = Vec<u32>;
+///     type PartOne = Option<u32>;
+///     type PartTwo = Option<u32>;
+///
+///     fn parse(input: &str) -> Self::Parsed {
+///         input.lines().map(|l| l.parse().unwrap()).collect()
+///     }
+///
+///     fn part_one(numbers: &Self::Parsed) -> Self::PartOne {
+///         numbers.iter().max().copied()
+///     }
+///
+///     fn part_two(numbers: &Self::Parsed) -> Self::PartTwo {
+///         None
+///     }
+/// }
+///
+/// fn main() {
+///     let input = &advent_of_code::read_input(7);
+///     advent_of_code::solve!(Day07, input);
+/// }
+/// ```
+pub trait Solution {
+    /// The parsed input. It is shared with the thread running a part when a time limit is set.
+    type Parsed: Send + Sync + 'static;
+    /// Return type of `part_one`, e.g. `Option<u32>` or `Result<u32, Box<dyn Error>>`.
+    type PartOne: IntoOutcome;
+    /// Return type of `part_two`.
+    type PartTwo: IntoOutcome;
+
+    fn parse(input: &str) -> Self::Parsed;
+    fn part_one(parsed: &Self::Parsed) -> Self::PartOne;
+    fn part_two(parsed: &Self::Parsed) -> Self::PartTwo;
+}
@@ -0,0 +1,623 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use crate::{
+    aoc_cli,
+    aoc_client::AocClient,
+    profiles,
+    puzzle::Puzzle,
+    results::{self, JsonValue},
+};
+use std::{
+    env,
+    error::Error,
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::PathBuf,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Whether a wrong answer was too high or too low.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+impl Hint {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Hint::TooHigh => "too_high",
+            Hint::TooLow => "too_low",
+        }
+    }
+}
+
+/// Response of the website to a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Response {
+    Correct,
+    /// The answer is wrong. The site asks to wait before trying again.
+    Wrong {
+        hint: Option<Hint>,
+        wait: Option<Duration>,
+    },
+    /// An answer was submitted too recently. The site did not check this one.
+    TooRecent(Duration),
+    /// The part is already solved or not unlocked yet.
+    WrongLevel,
+    /// The response could not be recognized. Holds its text.
+    Unknown(String),
+}
+
+impl Response {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Response::Correct => "correct",
+            Response::Wrong { .. } => "wrong",
+            Response::TooRecent(_) => "too_recent",
+            Response::WrongLevel => "wrong_level",
+            Response::Unknown(_) => "unknown",
+        }
+    }
+
+    /// Time to wait before the next answer may be submitted.
+    pub fn wait(&self) -> Option<Duration> {
+        match self {
+            Response::Wrong { wait, .. } => *wait,
+            Response::TooRecent(wait) => Some(*wait),
+            _ => None,
+        }
+    }
+
+    /// Classifies the text of a response page, as served by the site or printed by aoc-cli.
+    pub fn parse(text: &str) -> Response {
+        if text.contains("That's the right answer") {
+            Response::Correct
+        } else if text.contains("That's not the right answer") {
+            let hint = if text.contains("your answer is too high") {
+                Some(Hint::TooHigh)
+            } else if text.contains("your answer is too low") {
+                Some(Hint::TooLow)
+            } else {
+                None
+            };
+            Response::Wrong {
+                hint,
+                wait: parse_wait(text),
+            }
+        } else if text.contains("You gave an answer too recently") {
+            Response::TooRecent(parse_wait(text).unwrap_or(Duration::from_secs(60)))
+        } else if text.contains("You don't seem to be solving the right level") {
+            Response::WrongLevel
+        } else {
+            Response::Unknown(text.trim().to_string())
+        }
+    }
+}
+
+impl Display for Response {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Response::Correct => write!(f, "✔ That's the right answer!"),
+            Response::Wrong { hint, wait } => {
+                write!(f, "✘ That's not the right answer")?;
+                match hint {
+                    Some(Hint::TooHigh) => write!(f, ", it is too high")?,
+                    Some(Hint::TooLow) => write!(f, ", it is too low")?,
+                    None => {}
+                }
+                match wait {
+                    Some(wait) => write!(f, ". Wait {}s before trying again.", wait.as_secs()),
+                    None => write!(f, "."),
+                }
+            }
+            Response::TooRecent(wait) => write!(
+                f,
+                "You gave an answer too recently, wait {}s.",
+                wait.as_secs()
+            ),
+            Response::WrongLevel => write!(f, "This part is already solved or not unlocked yet."),
+            Response::Unknown(text) => write!(f, "Unknown response: {}", text),
+        }
+    }
+}
+
+/// Parses waits like "please wait one minute before trying again" or "You have 1m 20s left to wait".
+fn parse_wait(text: &str) -> Option<Duration> {
+    if let Some(end) = text.find(" left to wait") {
+        let start = text[..end].find("You have ")? + "You have ".len();
+        let mut secs = 0;
+        for part in text[start..end].split_whitespace() {
+            let (value, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+            let value: u64 = value.parse().ok()?;
+            secs += match unit {
+                "h" => value * 3600,
+                "m" => value * 60,
+                "s" => value,
+                _ => return None,
+            };
+        }
+        return Some(Duration::from_secs(secs));
+    }
+
+    let start = text.find("please wait ")? + "please wait ".len();
+    let mut words = text[start..].split_whitespace();
+    let value = match words.next()? {
+        "one" | "a" | "an" => 1,
+        word => word.parse().ok()?,
+    };
+    let unit = match words.next()?.trim_end_matches('s') {
+        "second" => 1,
+        "minute" => 60,
+        "hour" => 3600,
+        _ => return None,
+    };
+    Some(Duration::from_secs(value * unit))
+}
+
+/// Sends answers to the website. Implemented by the native client and by aoc-cli,
+/// and by fakes in tests.
+pub trait SubmitBackend {
+    fn submit(&self, puzzle: Puzzle, part: u8, answer: &str) -> Result<Response, Box<dyn Error>>;
+}
+
+impl SubmitBackend for AocClient {
+    fn submit(&self, puzzle: Puzzle, part: u8, answer: &str) -> Result<Response, Box<dyn Error>> {
+        let text = self.post_answer(puzzle, part, answer)?;
+        Ok(Response::parse(&text))
+    }
+}
+
+/// Submits answers by calling an installed aoc-cli.
+pub struct AocCli;
+
+impl SubmitBackend for AocCli {
+    fn submit(&self, puzzle: Puzzle, part: u8, answer: &str) -> Result<Response, Box<dyn Error>> {
+        let output = aoc_cli::submit(puzzle, part, answer)?;
+        Ok(Response::parse(&String::from_utf8_lossy(&output.stdout)))
+    }
+}
+
+/// A submitted answer and the site's response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attempt {
+    pub part: u8,
+    pub answer: String,
+    pub response: Response,
+    /// Seconds since the unix epoch.
+    pub submitted_at: u64,
+}
+
+impl Attempt {
+    /// Serializes the attempt as a single JSON Lines record, e.g.
+    /// `{"part":1,"answer":"24000","status":"wrong","hint":"too_high","submitted_at":1669870800,"wait_until":1669870860}`.
+    pub fn to_json(&self) -> String {
+        let hint = match &self.response {
+            Response::Wrong { hint, .. } => hint.map(|h| h.as_str()),
+            _ => None,
+        };
+        format!(
+            "{{\"part\":{},\"answer\":{},\"status\":\"{}\",\"hint\":{},\"submitted_at\":{},\"wait_until\":{}}}",
+            self.part,
+            results::json_string_or_null(Some(&self.answer)),
+            self.response.as_str(),
+            results::json_string_or_null(hint),
+            self.submitted_at,
+            results::json_number_or_null(self.wait_until()),
+        )
+    }
+
+    /// Parses a record previously written by [`Attempt::to_json`].
+    pub fn from_json(line: &str) -> Option<Attempt> {
+        let fields = results::parse_flat_object(line)?;
+        let get = |key: &str| fields.iter().find(|(k, _)| k == key).map(|(_, v)| v);
+        let string = |key: &str| match get(key) {
+            Some(JsonValue::String(val)) => Some(val.as_str()),
+            _ => None,
+        };
+
+        let submitted_at = get("submitted_at")?.as_number()?;
+        let wait = get("wait_until")
+            .and_then(JsonValue::as_number)
+            .map(|until| Duration::from_secs(until.saturating_sub(submitted_at)));
+
+        let response = match string("status")? {
+            "correct" => Response::Correct,
+            "wrong" => Response::Wrong {
+                hint: match string("hint") {
+                    Some("too_high") => Some(Hint::TooHigh),
+                    Some("too_low") => Some(Hint::TooLow),
+                    _ => None,
+                },
+                wait,
+            },
+            "too_recent" => Response::TooRecent(wait.unwrap_or_default()),
+            "wrong_level" => Response::WrongLevel,
+            _ => Response::Unknown(String::new()),
+        };
+
+        Some(Attempt {
+            part: get("part")?.as_number()?.try_into().ok()?,
+            answer: string("answer")?.to_string(),
+            response,
+            submitted_at,
+        })
+    }
+
+    fn wait_until(&self) -> Option<u64> {
+        self.response
+            .wait()
+            .map(|wait| self.submitted_at + wait.as_secs())
+    }
+}
+
+/// Why an answer was not submitted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Refusal {
+    AlreadySolved(String),
+    AlreadyWrong,
+    /// The answer is at least as high as an answer that was too high.
+    TooHigh(String),
+    /// The answer is at most as low as an answer that was too low.
+    TooLow(String),
+    Cooldown(Duration),
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadySolved(answer) => {
+                write!(f, "this part was already solved with {}.", answer)
+            }
+            Refusal::AlreadyWrong => write!(f, "this answer was already submitted and is wrong."),
+            Refusal::TooHigh(bound) => write!(f, "{} was already too high.", bound),
+            Refusal::TooLow(bound) => write!(f, "{} was already too low.", bound),
+            Refusal::Cooldown(wait) => write!(
+                f,
+                "the site asked to wait before trying again, {}s left.",
+                wait.as_secs()
+            ),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum SubmitError {
+    Refused(Refusal),
+    Backend(Box<dyn Error>),
+    IoError(io::Error),
+}
+
+impl Display for SubmitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubmitError::Refused(refusal) => write!(f, "not submitted, {}", refusal),
+            SubmitError::Backend(e) => write!(f, "could not submit: {}", e),
+            SubmitError::IoError(e) => write!(f, "could not write the submission ledger: {}", e),
+        }
+    }
+}
+
+impl Error for SubmitError {}
+
+/// Every answer submitted for a day, stored as JSON Lines in `src/submissions/DD.jsonl`
+/// or `src/submissions/YYYY/DD.jsonl`.
+///
+/// The site throttles submissions per account rather than per day, so the end of the last wait it
+/// asked for is also kept in `src/submissions/cooldown`, shared by every day and year of a profile.
+#[derive(Debug)]
+pub struct Ledger {
+    path: PathBuf,
+    attempts: Vec<Attempt>,
+    cooldown_path: PathBuf,
+    /// Seconds since the unix epoch until which the account may not submit.
+    cooldown_until: Option<u64>,
+}
+
+impl Ledger {
+    pub fn load(puzzle: impl Into<Puzzle>) -> io::Result<Ledger> {
+        let mut cooldown_path = env::current_dir()?.join("src").join("submissions");
+        if let Some(profile) = profiles::active() {
+            cooldown_path.push(profile);
+        }
+        Ledger::open(
+            puzzle.into().path("submissions", "jsonl")?,
+            cooldown_path.join("cooldown"),
+        )
+    }
+
+    /// Opens the ledger at `path` with the account's cooldown at `cooldown_path`.
+    /// Missing files mean nothing has been submitted yet.
+    pub fn open(path: PathBuf, cooldown_path: PathBuf) -> io::Result<Ledger> {
+        let attempts = match fs::read_to_string(&path) {
+            Ok(contents) => contents.lines().filter_map(Attempt::from_json).collect(),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => vec![],
+            Err(e) => return Err(e),
+        };
+        let cooldown_until = match fs::read_to_string(&cooldown_path) {
+            Ok(contents) => contents.trim().parse().ok(),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+            Err(e) => return Err(e),
+        };
+        Ok(Ledger {
+            path,
+            attempts,
+            cooldown_path,
+            cooldown_until,
+        })
+    }
+
+    pub fn attempts(&self) -> &[Attempt] {
+        &self.attempts
+    }
+
+    /// Appends an attempt to the ledger file, and moves the account's cooldown
+    /// if the site asked to wait longer.
+    pub fn record(&mut self, attempt: Attempt) -> io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{}", attempt.to_json())?;
+
+        if let Some(until) = attempt.wait_until() {
+            if self.cooldown_until.is_none_or(|current| until > current) {
+                if let Some(parent) = self.cooldown_path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::write(&self.cooldown_path, format!("{}\n", until))?;
+                self.cooldown_until = Some(until);
+            }
+        }
+
+        self.attempts.push(attempt);
+        Ok(())
+    }
+
+    /// Checks whether `answer` may be submitted for `part` at `now`.
+    pub fn check(&self, part: u8, answer: &str, now: SystemTime) -> Result<(), Refusal> {
+        let attempts = self.attempts.iter().filter(|a| a.part == part);
+
+        for attempt in attempts.clone() {
+            match &attempt.response {
+                Response::Correct => return Err(Refusal::AlreadySolved(attempt.answer.clone())),
+                Response::Wrong { .. } if attempt.answer == answer => {
+                    return Err(Refusal::AlreadyWrong)
+                }
+                _ => {}
+            }
+        }
+
+        if let Ok(value) = answer.parse::<i64>() {
+            let bound = |hint: Hint| {
+                attempts.clone().filter_map(move |a| match a.response {
+                    Response::Wrong { hint: Some(h), .. } if h == hint => {
+                        a.answer.parse::<i64>().ok()
+                    }
+                    _ => None,
+                })
+            };
+
+            if let Some(high) = bound(Hint::TooHigh).min().filter(|high| value >= *high) {
+                return Err(Refusal::TooHigh(high.to_string()));
+            }
+            if let Some(low) = bound(Hint::TooLow).max().filter(|low| value <= *low) {
+                return Err(Refusal::TooLow(low.to_string()));
+            }
+        }
+
+        let now = unix_secs(now);
+        let until = self.attempts.iter().filter_map(Attempt::wait_until).max();
+        if let Some(until) = until.max(self.cooldown_until) {
+            if until > now {
+                return Err(Refusal::Cooldown(Duration::from_secs(until - now)));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn unix_secs(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Submits `answer` unless the ledger shows it cannot be right, and records the response.
+pub fn submit(
+    backend: &dyn SubmitBackend,
+    ledger: &mut Ledger,
+    puzzle: Puzzle,
+    part: u8,
+    answer: &str,
+    now: SystemTime,
+) -> Result<Response, SubmitError> {
+    ledger
+        .check(part, answer, now)
+        .map_err(SubmitError::Refused)?;
+
+    let response = backend
+        .submit(puzzle, part, answer)
+        .map_err(SubmitError::Backend)?;
+
+    ledger
+        .record(Attempt {
+            part,
+            answer: answer.to_string(),
+            response: response.clone(),
+            submitted_at: unix_secs(now),
+        })
+        .map_err(SubmitError::IoError)?;
+
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    /// Answers every submission with the next canned response.
+    struct Fake {
+        responses: RefCell<Vec<Response>>,
+        submitted: RefCell<Vec<String>>,
+    }
+
+    impl Fake {
+        fn new(mut responses: Vec<Response>) -> Fake {
+            responses.reverse();
+            Fake {
+                responses: RefCell::new(responses),
+                submitted: RefCell::new(vec![]),
+            }
+        }
+    }
+
+    impl SubmitBackend for Fake {
+        fn submit(&self, _: Puzzle, _: u8, answer: &str) -> Result<Response, Box<dyn Error>> {
+            self.submitted.borrow_mut().push(answer.to_string());
+            self.responses
+                .borrow_mut()
+                .pop()
+                .ok_or_else(|| "no response left".into())
+        }
+    }
+
+    fn at(secs: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(secs)
+    }
+
+    fn wrong(hint: Option<Hint>, wait: u64) -> Response {
+        Response::Wrong {
+            hint,
+            wait: Some(Duration::from_secs(wait)),
+        }
+    }
+
+    #[test]
+    fn test_parse_response() {
+        assert_eq!(
+            Response::parse("<p>That's the right answer! You are one gold star closer.</p>"),
+            Response::Correct
+        );
+        assert_eq!(
+            Response::parse("That's not the right answer; your answer is too high. If you're stuck, ... please wait one minute before trying again."),
+            wrong(Some(Hint::TooHigh), 60)
+        );
+        assert_eq!(
+            Response::parse("That's not the right answer. Because you have guessed incorrectly 4 times on this puzzle, please wait 5 minutes before trying again."),
+            wrong(None, 300)
+        );
+        assert_eq!(
+            Response::parse("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 20s left to wait."),
+            Response::TooRecent(Duration::from_secs(80))
+        );
+        assert_eq!(
+            Response::parse(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            ),
+            Response::WrongLevel
+        );
+        assert!(matches!(Response::parse("<html>"), Response::Unknown(_)));
+    }
+
+    #[test]
+    fn test_json_roundtrip() {
+        let attempt = Attempt {
+            part: 1,
+            answer: "24000".into(),
+            response: wrong(Some(Hint::TooHigh), 60),
+            submitted_at: 1_669_870_800,
+        };
+        assert_eq!(
+            attempt.to_json(),
+            r#"{"part":1,"answer":"24000","status":"wrong","hint":"too_high","submitted_at":1669870800,"wait_until":1669870860}"#
+        );
+        assert_eq!(Attempt::from_json(&attempt.to_json()), Some(attempt));
+    }
+
+    #[test]
+    fn test_submit() {
+        let path = env::temp_dir().join(format!("aoc-ledger-{}.jsonl", std::process::id()));
+        let cooldown = env::temp_dir().join(format!("aoc-cooldown-{}", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let _ = fs::remove_file(&cooldown);
+        let mut ledger = Ledger::open(path.clone(), cooldown.clone()).unwrap();
+        let puzzle = Puzzle::from(1);
+        let fake = Fake::new(vec![
+            wrong(Some(Hint::TooHigh), 60),
+            wrong(Some(Hint::TooLow), 60),
+            Response::Correct,
+        ]);
+
+        let mut try_submit =
+            |answer: &str, secs: u64| submit(&fake, &mut ledger, puzzle, 1, answer, at(secs));
+
+        assert_eq!(
+            try_submit("500", 0).unwrap(),
+            wrong(Some(Hint::TooHigh), 60)
+        );
+        assert!(matches!(
+            try_submit("400", 30),
+            Err(SubmitError::Refused(Refusal::Cooldown(wait))) if wait.as_secs() == 30
+        ));
+        assert!(matches!(
+            try_submit("500", 100),
+            Err(SubmitError::Refused(Refusal::AlreadyWrong))
+        ));
+        assert!(matches!(
+            try_submit("600", 100),
+            Err(SubmitError::Refused(Refusal::TooHigh(_)))
+        ));
+        assert_eq!(
+            try_submit("100", 100).unwrap(),
+            wrong(Some(Hint::TooLow), 60)
+        );
+        assert!(matches!(
+            try_submit("50", 200),
+            Err(SubmitError::Refused(Refusal::TooLow(_)))
+        ));
+        assert_eq!(try_submit("300", 200).unwrap(), Response::Correct);
+        assert!(matches!(
+            try_submit("300", 300),
+            Err(SubmitError::Refused(Refusal::AlreadySolved(_)))
+        ));
+        assert_eq!(*fake.submitted.borrow(), ["500", "100", "300"]);
+
+        let reloaded = Ledger::open(path.clone(), cooldown.clone()).unwrap();
+        assert_eq!(reloaded.attempts(), ledger.attempts());
+        assert_eq!(reloaded.attempts().len(), 3);
+        fs::remove_file(path).unwrap();
+        fs::remove_file(cooldown).unwrap();
+    }
+
+    #[test]
+    fn test_account_cooldown() {
+        let dir = env::temp_dir().join(format!("aoc-cooldown-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let cooldown = dir.join("cooldown");
+        let fake = Fake::new(vec![wrong(None, 60), Response::Correct]);
+
+        let mut day_one = Ledger::open(dir.join("01.jsonl"), cooldown.clone()).unwrap();
+        submit(&fake, &mut day_one, Puzzle::from(1), 1, "1", at(0)).unwrap();
+
+        let mut day_two = Ledger::open(dir.join("02.jsonl"), cooldown.clone()).unwrap();
+        assert!(matches!(
+            submit(&fake, &mut day_two, Puzzle::from(2), 1, "2", at(30)),
+            Err(SubmitError::Refused(Refusal::Cooldown(wait))) if wait.as_secs() == 30
+        ));
+        assert_eq!(
+            submit(&fake, &mut day_two, Puzzle::from(2), 1, "2", at(60)).unwrap(),
+            Response::Correct
+        );
+        assert_eq!(fs::read_to_string(&cooldown).unwrap(), "60\n");
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
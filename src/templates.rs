@@ -0,0 +1,256 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use crate::puzzle::{self, Puzzle};
+use std::{fs, io, path::PathBuf, time::SystemTime};
+
+/// Directory of the templates used by `cargo scaffold`, e.g. `src/templates/default.rs`.
+pub const TEMPLATES_DIR: &str = "src/templates";
+/// Template used when `--template` is not passed.
+pub const DEFAULT_TEMPLATE: &str = "default";
+/// Answer type used when `--answer-type` is not passed.
+pub const DEFAULT_ANSWER_TYPE: &str = "u32";
+
+const MODULE_TEMPLATE: &str = r###"pub fn part_one(input: &str) -> Option<{{answer_type}}> {
+    None
+}
+
+pub fn part_two(input: &str) -> Option<{{answer_type}}> {
+    None
+}
+
+fn main() {
+    let input = &advent_of_code::read_input({{puzzle}});
+    advent_of_code::solve!(1, part_one, input);
+    advent_of_code::solve!(2, part_two, input);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = advent_of_code::read_file("examples", {{puzzle}});
+        assert_eq!(part_one(&input), None);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = advent_of_code::read_file("examples", {{puzzle}});
+        assert_eq!(part_two(&input), None);
+    }
+}
+"###;
+
+/// Variant of `MODULE_TEMPLATE` for `--result`, whose parts return errors instead of `None`.
+const RESULT_MODULE_TEMPLATE: &str = r###"use advent_of_code::results::Unsolved;
+use std::error::Error;
+
+pub fn part_one(input: &str) -> Result<{{answer_type}}, Box<dyn Error>> {
+    Err(Unsolved.into())
+}
+
+pub fn part_two(input: &str) -> Result<{{answer_type}}, Box<dyn Error>> {
+    Err(Unsolved.into())
+}
+
+fn main() {
+    let input = &advent_of_code::read_input({{puzzle}});
+    advent_of_code::solve!(1, part_one, input);
+    advent_of_code::solve!(2, part_two, input);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = advent_of_code::read_file("examples", {{puzzle}});
+        assert_eq!(part_one(&input).ok(), None);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = advent_of_code::read_file("examples", {{puzzle}});
+        assert_eq!(part_two(&input).ok(), None);
+    }
+}
+"###;
+
+/// Returns the template shipped with the repository for `name`.
+pub fn builtin(name: &str) -> Option<&'static str> {
+    match name {
+        "default" => Some(MODULE_TEMPLATE),
+        "result" => Some(RESULT_MODULE_TEMPLATE),
+        _ => None,
+    }
+}
+
+pub fn path(name: &str) -> PathBuf {
+    PathBuf::from(TEMPLATES_DIR).join(format!("{}.rs", name))
+}
+
+/// Loads `src/templates/<name>.rs`, falling back to the built-in template of that name.
+pub fn load(name: &str) -> io::Result<String> {
+    match fs::read_to_string(path(name)) {
+        Ok(template) => Ok(template),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => match builtin(name) {
+            Some(template) => Ok(template.to_string()),
+            None => Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("no template named \"{}\" in \"{}\"", name, TEMPLATES_DIR),
+            )),
+        },
+        Err(e) => Err(e),
+    }
+}
+
+/// Values of the placeholders of a template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Placeholders {
+    pub puzzle: Puzzle,
+    pub title: Option<String>,
+    pub answer_type: String,
+}
+
+impl Placeholders {
+    /// Replaces the placeholders of `template`:
+    ///
+    /// - `{{day}}`: the day, e.g. `7`
+    /// - `{{day_padded}}`: the zero-padded day, e.g. `07`
+    /// - `{{year}}`: the year of the day, or of the current event for days without a year
+    /// - `{{puzzle}}`: the argument of `read_input` and `read_file`, e.g. `7` or `(2021, 7)`
+    /// - `{{title}}`: the puzzle title if it was downloaded, e.g. `No Space Left On Device`
+    /// - `{{answer_type}}`: the return type of the parts, e.g. `u32`
+    pub fn render(&self, template: &str) -> String {
+        let Puzzle { year, day } = self.puzzle;
+
+        let puzzle = match year {
+            Some(year) => format!("({}, {})", year, day),
+            None => day.to_string(),
+        };
+        let year = year.unwrap_or_else(|| puzzle::event_year(SystemTime::now()));
+        let title = match &self.title {
+            Some(title) => title.clone(),
+            None => format!("Day {}", day),
+        };
+
+        [
+            ("{{day}}", day.to_string()),
+            ("{{day_padded}}", format!("{:02}", day)),
+            ("{{year}}", year.to_string()),
+            ("{{puzzle}}", puzzle),
+            ("{{title}}", title),
+            ("{{answer_type}}", self.answer_type.clone()),
+        ]
+        .iter()
+        .fold(template.to_string(), |rendered, (placeholder, value)| {
+            rendered.replace(placeholder, value)
+        })
+    }
+}
+
+/// Tells how `cargo all` runs a rendered module: through the type that implements `Solution`,
+/// or through `pub fn part_one` and `pub fn part_two` when this returns `None`.
+pub fn solution_type(module: &str) -> Result<Option<String>, String> {
+    let implemented = module
+        .lines()
+        .filter(|line| line.trim_start().starts_with("impl"))
+        .filter_map(|line| line.split_once("Solution for ").map(|(_, rest)| rest))
+        .map(|rest| {
+            rest.chars()
+                .take_while(|c| c.is_alphanumeric() || *c == '_')
+                .collect::<String>()
+        })
+        .find(|name| !name.is_empty());
+    if implemented.is_some() {
+        return Ok(implemented);
+    }
+
+    let has_part = |name: &str| {
+        let signature = format!("pub fn {}(", name);
+        module
+            .lines()
+            .any(|line| line.trim_start().starts_with(&signature))
+    };
+    if has_part("part_one") && has_part("part_two") {
+        Ok(None)
+    } else {
+        Err(
+            "the template neither implements `Solution` nor has `pub fn part_one` and `pub fn part_two`, one of which is needed to register the day"
+                .into(),
+        )
+    }
+}
+
+/// Reads the title from the heading of a puzzle description, e.g. `--- Day 7: No Space Left On Device ---`.
+pub fn title(markdown: &str) -> Option<String> {
+    let heading = markdown.lines().find(|l| l.contains("--- Day "))?;
+    let (_, title) = heading.split_once(": ")?;
+    let title = title.trim().trim_end_matches('-').trim();
+    Some(title.to_string()).filter(|t| !t.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let placeholders = Placeholders {
+            puzzle: Puzzle::from((2021, 7)),
+            title: Some("The Treachery of Whales".into()),
+            answer_type: "u64".into(),
+        };
+        assert_eq!(
+            placeholders.render(
+                "// {{year}} day {{day}} ({{day_padded}}): {{title}}\nfn part_one() -> Option<{{answer_type}}> {}\nread_input({{puzzle}});"
+            ),
+            "// 2021 day 7 (07): The Treachery of Whales\nfn part_one() -> Option<u64> {}\nread_input((2021, 7));"
+        );
+
+        let placeholders = Placeholders {
+            puzzle: Puzzle::from(7),
+            title: None,
+            answer_type: DEFAULT_ANSWER_TYPE.into(),
+        };
+        let rendered = placeholders.render(builtin(DEFAULT_TEMPLATE).unwrap());
+        assert!(rendered.contains("pub fn part_one(input: &str) -> Option<u32> {"));
+        assert!(rendered.contains("advent_of_code::read_input(7);"));
+        assert!(!rendered.contains("{{"));
+        assert_eq!(placeholders.render("{{title}}"), "Day 7");
+    }
+
+    #[test]
+    fn test_title() {
+        assert_eq!(
+            title("\\--- Day 7: No Space Left On Device ---\n----------\n").as_deref(),
+            Some("No Space Left On Device")
+        );
+        assert_eq!(
+            title("## --- Day 1: Calorie Counting ---\n").as_deref(),
+            Some("Calorie Counting")
+        );
+        assert_eq!(title("no heading"), None);
+    }
+
+    #[test]
+    fn test_solution_type() {
+        assert_eq!(solution_type(MODULE_TEMPLATE), Ok(None));
+        assert_eq!(solution_type(RESULT_MODULE_TEMPLATE), Ok(None));
+        assert_eq!(
+            solution_type("pub struct Day07;\n\nimpl advent_of_code::Solution for Day07 {\n}\n"),
+            Ok(Some("Day07".into()))
+        );
+        assert!(solution_type("pub fn part_one(input: &str) -> Option<u32> {}\n").is_err());
+    }
+
+    #[test]
+    fn test_load() {
+        assert_eq!(load("result").unwrap(), RESULT_MODULE_TEMPLATE);
+        assert!(load("missing").is_err());
+    }
+}
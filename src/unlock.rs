@@ -0,0 +1,196 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::{
+    thread,
+    time::{Duration, SystemTime},
+};
+
+/// Delays between download attempts once a puzzle unlocked. The site can take a moment to serve it.
+pub const BACKOFF: [Duration; 5] = [
+    Duration::from_secs(1),
+    Duration::from_secs(2),
+    Duration::from_secs(4),
+    Duration::from_secs(8),
+    Duration::from_secs(16),
+];
+
+/// Source of time for `wait_until` and `retry`, replaced by a fake one in tests.
+pub trait Clock {
+    fn now(&self) -> SystemTime;
+    fn sleep(&self, duration: Duration);
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration)
+    }
+}
+
+/// Time left until `instant`, zero if it passed.
+pub fn remaining(clock: &impl Clock, instant: SystemTime) -> Duration {
+    instant.duration_since(clock.now()).unwrap_or_default()
+}
+
+/// Sleeps until `instant`, calling `tick` with the time left at most once a second.
+pub fn wait_until(clock: &impl Clock, instant: SystemTime, mut tick: impl FnMut(Duration)) {
+    loop {
+        let left = remaining(clock, instant);
+        if left.is_zero() {
+            return;
+        }
+        tick(left);
+        // sleep up to the next full second, so the countdown ticks over on time.
+        let step = Duration::from_nanos(u64::from(left.subsec_nanos()));
+        clock.sleep(if step.is_zero() {
+            Duration::from_secs(1)
+        } else {
+            step
+        });
+    }
+}
+
+/// Calls `attempt` until it succeeds, sleeping between tries as given by `backoff`.
+/// Errors for which `retryable` is false, and the error after the last delay, are returned.
+pub fn retry<T, E>(
+    clock: &impl Clock,
+    backoff: &[Duration],
+    mut attempt: impl FnMut() -> Result<T, E>,
+    retryable: impl Fn(&E) -> bool,
+) -> Result<T, E> {
+    let mut delays = backoff.iter();
+    loop {
+        match attempt() {
+            Err(e) if retryable(&e) => match delays.next() {
+                Some(delay) => clock.sleep(*delay),
+                None => return Err(e),
+            },
+            result => return result,
+        }
+    }
+}
+
+/// Formats a countdown as `1d 02:03:04`, or `02:03:04` when less than a day is left.
+pub fn format_countdown(left: Duration) -> String {
+    // round up, so "00:00:00" is only shown once the puzzle unlocked.
+    let secs = left.as_secs() + u64::from(left.subsec_nanos() > 0);
+    let (days, secs) = (secs / 86_400, secs % 86_400);
+    let time = format!(
+        "{:02}:{:02}:{:02}",
+        secs / 3600,
+        secs % 3600 / 60,
+        secs % 60
+    );
+    if days > 0 {
+        format!("{}d {}", days, time)
+    } else {
+        time
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{cell::RefCell, time::UNIX_EPOCH};
+
+    struct FakeClock {
+        now: RefCell<SystemTime>,
+        sleeps: RefCell<Vec<Duration>>,
+    }
+
+    impl FakeClock {
+        fn at(secs: u64) -> FakeClock {
+            FakeClock {
+                now: RefCell::new(UNIX_EPOCH + Duration::from_secs(secs)),
+                sleeps: RefCell::new(vec![]),
+            }
+        }
+    }
+
+    impl Clock for FakeClock {
+        fn now(&self) -> SystemTime {
+            *self.now.borrow()
+        }
+
+        fn sleep(&self, duration: Duration) {
+            *self.now.borrow_mut() += duration;
+            self.sleeps.borrow_mut().push(duration);
+        }
+    }
+
+    #[test]
+    fn test_wait_until() {
+        let clock = FakeClock::at(100);
+        clock.sleep(Duration::from_millis(500));
+        let unlock = UNIX_EPOCH + Duration::from_secs(103);
+
+        let mut ticks = vec![];
+        wait_until(&clock, unlock, |left| ticks.push(format_countdown(left)));
+
+        assert_eq!(clock.now(), unlock);
+        assert_eq!(ticks, ["00:00:03", "00:00:02", "00:00:01"]);
+        assert_eq!(
+            clock.sleeps.borrow()[1..],
+            [
+                Duration::from_millis(500),
+                Duration::from_secs(1),
+                Duration::from_secs(1)
+            ]
+        );
+
+        let mut ticks = 0;
+        wait_until(&clock, UNIX_EPOCH, |_| ticks += 1);
+        assert_eq!(ticks, 0);
+    }
+
+    #[test]
+    fn test_retry() {
+        let clock = FakeClock::at(0);
+        let mut attempts = 0;
+        let result = retry(
+            &clock,
+            &BACKOFF,
+            || {
+                attempts += 1;
+                if attempts < 3 {
+                    Err("locked")
+                } else {
+                    Ok(attempts)
+                }
+            },
+            |e| *e == "locked",
+        );
+        assert_eq!(result, Ok(3));
+        assert_eq!(
+            *clock.sleeps.borrow(),
+            [Duration::from_secs(1), Duration::from_secs(2)]
+        );
+
+        let clock = FakeClock::at(0);
+        let result: Result<(), _> = retry(&clock, &BACKOFF, || Err("locked"), |e| *e == "locked");
+        assert_eq!(result, Err("locked"));
+        assert_eq!(clock.sleeps.borrow().len(), BACKOFF.len());
+
+        let clock = FakeClock::at(0);
+        let result: Result<(), _> = retry(&clock, &BACKOFF, || Err("fatal"), |e| *e == "locked");
+        assert_eq!(result, Err("fatal"));
+        assert!(clock.sleeps.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_format_countdown() {
+        assert_eq!(format_countdown(Duration::from_secs(3723)), "01:02:03");
+        assert_eq!(format_countdown(Duration::from_millis(1)), "00:00:01");
+        assert_eq!(
+            format_countdown(Duration::from_secs(2 * 86_400 + 59)),
+            "2d 00:00:59"
+        );
+    }
+}